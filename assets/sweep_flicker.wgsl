@@ -0,0 +1,27 @@
+// The material of the custom_material_flicker example, draws a band of the flicker color that
+// sweeps down the sprite as the flicker progresses.
+#import bevy_sprite::mesh2d_vertex_output::VertexOutput
+
+@group(2) @binding(0)
+var source_texture: texture_2d<f32>;
+
+@group(2) @binding(1)
+var source_sampler: sampler;
+
+struct SweepMaterial {
+    offset: vec2<f32>,
+    size: vec2<f32>,
+    color: vec4<f32>,
+    progress: f32,
+}
+
+@group(2) @binding(2)
+var<uniform> material: SweepMaterial;
+
+@fragment
+fn fragment(in: VertexOutput) -> @location(0) vec4<f32> {
+    let uv = in.uv * material.size + material.offset;
+    let texel = textureSample(source_texture, source_sampler, uv);
+    let band = 1.0 - smoothstep(0.0, 0.2, abs(in.uv.y - material.progress));
+    return vec4<f32>(material.color.rgb, material.color.a * texel.a * band);
+}
@@ -0,0 +1,72 @@
+// A sprite that cycles through the frames of its sheet faster than it flickers, the overlay
+// follows the current frame for the whole flicker.
+use bevy::prelude::*;
+use bevy_flicker::prelude::*;
+
+const FIXED_TIMESTEP: f64 = 1.0;
+const FLICKER_LENGTH: f32 = 0.6;
+const FRAME_LENGTH: f32 = 0.1;
+
+#[derive(Component)]
+pub struct Animation(Timer);
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(FlickerPlugin)
+        .add_systems(Startup, setup)
+        .add_systems(Update, animate)
+        .add_systems(FixedUpdate, tick)
+        .insert_resource(Time::<Fixed>::from_seconds(FIXED_TIMESTEP))
+        .run();
+}
+
+fn setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut texture_atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
+) {
+    let texture = asset_server.load("asteroid_sheet_test.png");
+    let atlas_layout = texture_atlas_layouts.add(TextureAtlasLayout::from_grid(
+        UVec2::new(64, 64),
+        1,
+        4,
+        None,
+        None,
+    ));
+    commands.spawn(Camera2d::default());
+    commands.spawn((
+        Sprite {
+            texture_atlas: Some(TextureAtlas {
+                index: 0,
+                layout: atlas_layout,
+            }),
+            image: texture,
+            ..default()
+        },
+        Transform::default().with_scale(Vec3::splat(8.0)),
+        Animation(Timer::from_seconds(FRAME_LENGTH, TimerMode::Repeating)),
+    ));
+}
+
+fn animate(mut query: Query<(&mut Sprite, &mut Animation)>, time: Res<Time>) {
+    for (mut sprite, mut animation) in query.iter_mut() {
+        animation.0.tick(time.delta());
+        if animation.0.just_finished() {
+            if let Some(atlas) = sprite.texture_atlas.as_mut() {
+                atlas.index = (atlas.index + 1) % 4;
+            }
+        }
+    }
+}
+
+fn tick(query: Query<Entity, With<Animation>>, mut event_writer: MessageWriter<FlickerStartEvent>) {
+    for e in query.iter() {
+        event_writer.write(
+            FlickerStartEvent::builder(e)
+                .with_secs(FLICKER_LENGTH)
+                .with_color(LinearRgba::new(1.0, 0.0, 0.0, 0.5).into())
+                .build(),
+        );
+    }
+}
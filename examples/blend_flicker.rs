@@ -0,0 +1,51 @@
+// Shows each of the blend modes, from left to right: replace, multiply, add, screen, overlay and
+// tint.
+use bevy::prelude::*;
+use bevy_flicker::prelude::*;
+
+const FIXED_TIMESTEP: f64 = 1.0;
+const FLICKER_LENGTH: f32 = 0.5;
+
+#[derive(Component)]
+pub struct Marker(FlickerBlendMode);
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(FlickerPlugin)
+        .add_systems(Startup, setup)
+        .add_systems(FixedUpdate, tick)
+        .insert_resource(Time::<Fixed>::from_seconds(FIXED_TIMESTEP))
+        .run();
+}
+
+fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.spawn(Camera2d::default());
+    let blend_modes = [
+        FlickerBlendMode::Replace,
+        FlickerBlendMode::Multiply,
+        FlickerBlendMode::Add,
+        FlickerBlendMode::Screen,
+        FlickerBlendMode::Overlay,
+        FlickerBlendMode::Tint,
+    ];
+    for (i, blend_mode) in blend_modes.into_iter().enumerate() {
+        commands.spawn((
+            Sprite::from_image(asset_server.load("asteroid5.png")),
+            Transform::from_xyz(-500.0 + i as f32 * 200.0, 0.0, 0.0).with_scale(Vec3::splat(3.0)),
+            Marker(blend_mode),
+        ));
+    }
+}
+
+fn tick(query: Query<(Entity, &Marker)>, mut event_writer: MessageWriter<FlickerStartEvent>) {
+    for (e, marker) in query.iter() {
+        event_writer.write(
+            FlickerStartEvent::builder(e)
+                .with_secs(FLICKER_LENGTH)
+                .with_color(LinearRgba::new(1.0, 0.2, 0.2, 0.8).into())
+                .with_blend_mode(marker.0)
+                .build(),
+        );
+    }
+}
@@ -0,0 +1,69 @@
+// Draws 2D overlays with a custom material instead of the built-in one, while flickers are still
+// started with events and RepeatingFlicker as usual. The material's shader is in
+// assets/sweep_flicker.wgsl and sweeps a band of the flicker color down the sprite.
+use bevy::prelude::*;
+use bevy::render::render_resource::AsBindGroup;
+use bevy::shader::ShaderRef;
+use bevy::sprite_render::{AlphaMode2d, Material2d};
+use bevy_flicker::prelude::*;
+
+#[derive(Asset, AsBindGroup, TypePath, Clone)]
+struct SweepMaterial {
+    #[texture(0)]
+    #[sampler(1)]
+    source_image: Option<Handle<Image>>,
+    #[uniform(2)]
+    offset: Vec2,
+    #[uniform(2)]
+    size: Vec2,
+    #[uniform(2)]
+    color: LinearRgba,
+    #[uniform(2)]
+    progress: f32,
+}
+
+impl Material2d for SweepMaterial {
+    fn fragment_shader() -> ShaderRef {
+        "sweep_flicker.wgsl".into()
+    }
+    fn alpha_mode(&self) -> AlphaMode2d {
+        AlphaMode2d::Blend
+    }
+}
+
+impl FlickerShader for SweepMaterial {
+    fn from_inputs(inputs: &FlickerInputs) -> Self {
+        Self {
+            source_image: inputs.source_image.clone(),
+            offset: inputs.offset,
+            size: inputs.size,
+            color: inputs.color,
+            progress: inputs.progress,
+        }
+    }
+
+    fn set_inputs(&mut self, inputs: &FlickerInputs) {
+        *self = Self::from_inputs(inputs);
+    }
+}
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(FlickerPlugin::with_material::<SweepMaterial>())
+        .add_systems(Startup, setup)
+        .run();
+}
+
+fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.spawn(Camera2d::default());
+    commands.spawn((
+        Sprite::from_image(asset_server.load("asteroid_round.png")),
+        Transform::from_scale(Vec3::splat(2.0)),
+        RepeatingFlicker::builder()
+            .with_color(Color::srgba(0.4, 0.9, 1.0, 0.9))
+            .with_flicker_time_length(0.8)
+            .with_time_between_pulses(0.4)
+            .build(),
+    ));
+}
@@ -0,0 +1,51 @@
+// Flickers that dissolve away instead of fading, using the built-in noise. Press space to hit the
+// asteroids. The one on the left uses fine noise for a static look, the one on the right coarser
+// noise that breaks up in chunks. A noise image can be used instead by passing its handle, such
+// as `noise: Some(asset_server.load("noise.png"))`.
+use bevy::prelude::*;
+use bevy_flicker::prelude::*;
+
+#[derive(Component)]
+struct NoiseScale(f32);
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(FlickerPlugin)
+        .add_systems(Startup, setup)
+        .add_systems(Update, hit)
+        .run();
+}
+
+fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.spawn(Camera2d::default());
+    for (x, scale) in [(-200.0, 1.0), (200.0, 4.0)] {
+        commands.spawn((
+            Sprite::from_image(asset_server.load("asteroid5.png")),
+            Transform::from_xyz(x, 0.0, 0.0).with_scale(Vec3::splat(6.0)),
+            NoiseScale(scale),
+        ));
+    }
+}
+
+fn hit(
+    keys: Res<ButtonInput<KeyCode>>,
+    query: Query<(Entity, &NoiseScale)>,
+    mut event_writer: MessageWriter<FlickerStartEvent>,
+) {
+    if !keys.just_pressed(KeyCode::Space) {
+        return;
+    }
+    for (entity, scale) in query.iter() {
+        event_writer.write(
+            FlickerStartEvent::builder(entity)
+                .with_secs(0.6)
+                .with_color(Color::srgb(1.0, 0.5, 0.1))
+                .with_style(FlickerStyle::Dissolve {
+                    noise: None,
+                    scale: scale.0,
+                })
+                .build(),
+        );
+    }
+}
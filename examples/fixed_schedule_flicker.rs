@@ -0,0 +1,56 @@
+// Runs the flicker systems in FixedUpdate at a low rate, so each flicker advances in the same
+// whole steps no matter the frame rate. Every start and end is logged with the fixed tick it
+// happened on, and each flicker lasts exactly the same number of ticks, which is what lets
+// rollback re-simulation reproduce them.
+use bevy::prelude::*;
+use bevy_flicker::prelude::*;
+
+const FIXED_TIMESTEP: f64 = 1.0 / 20.0;
+
+#[derive(Resource, Default)]
+struct FixedTick(u64);
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(FlickerPlugin.with_schedule(FixedUpdate))
+        .insert_resource(Time::<Fixed>::from_seconds(FIXED_TIMESTEP))
+        .init_resource::<FixedTick>()
+        .add_systems(Startup, setup)
+        .add_systems(
+            FixedUpdate,
+            (count_ticks.before(FlickerSet), log.after(FlickerSet)),
+        )
+        .run();
+}
+
+fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.spawn(Camera2d::default());
+    commands.spawn((
+        Sprite::from_image(asset_server.load("asteroid5.png")),
+        Transform::from_scale(Vec3::splat(4.0)),
+        RepeatingFlicker::builder()
+            .with_color(Color::WHITE)
+            .with_intensity(FlickerIntensity::FadeOut)
+            .with_flicker_time_length(0.5)
+            .with_time_between_pulses(0.25)
+            .build(),
+    ));
+}
+
+fn count_ticks(mut tick: ResMut<FixedTick>) {
+    tick.0 += 1;
+}
+
+fn log(
+    tick: Res<FixedTick>,
+    mut started: MessageReader<FlickerStarted>,
+    mut ended: MessageReader<FlickerEnded>,
+) {
+    for _ in started.read() {
+        info!("Flicker started on tick {}", tick.0);
+    }
+    for _ in ended.read() {
+        info!("Flicker ended on tick {}", tick.0);
+    }
+}
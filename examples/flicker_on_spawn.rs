@@ -0,0 +1,40 @@
+// Press space to spawn an asteroid at a random spot, each one flashes white as it appears.
+use bevy::prelude::*;
+use bevy_flicker::prelude::*;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(FlickerPlugin)
+        .add_systems(Startup, setup)
+        .add_systems(Update, spawn_asteroid)
+        .run();
+}
+
+fn setup(mut commands: Commands) {
+    commands.spawn(Camera2d::default());
+}
+
+fn spawn_asteroid(
+    keys: Res<ButtonInput<KeyCode>>,
+    asset_server: Res<AssetServer>,
+    time: Res<Time>,
+    mut commands: Commands,
+) {
+    if !keys.just_pressed(KeyCode::Space) {
+        return;
+    }
+    let t = time.elapsed_secs();
+    let position = Vec2::new((t * 7.3).sin() * 400.0, (t * 3.1).cos() * 250.0);
+    commands.spawn((
+        // The flicker waits for the image to load before it starts
+        Sprite::from_image(asset_server.load("asteroid5.png")),
+        Transform::from_translation(position.extend(0.0)).with_scale(Vec3::splat(2.0)),
+        FlickerOnSpawn {
+            secs: 0.4,
+            color: Color::WHITE,
+            intensity: FlickerIntensity::FadeOut,
+            ..default()
+        },
+    ));
+}
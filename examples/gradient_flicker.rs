@@ -0,0 +1,62 @@
+// A "burning" flicker that moves from white to red to transparent, and a "freezing" repeating
+// flicker that shifts hue through HSV.
+use bevy::prelude::*;
+use bevy_flicker::prelude::*;
+
+const FIXED_TIMESTEP: f64 = 1.5;
+const FLICKER_LENGTH: f32 = 1.0;
+
+#[derive(Component, Default)]
+pub struct Marker;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(FlickerPlugin)
+        .add_systems(Startup, setup)
+        .add_systems(FixedUpdate, tick)
+        .insert_resource(Time::<Fixed>::from_seconds(FIXED_TIMESTEP))
+        .run();
+}
+
+fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.spawn(Camera2d::default());
+    commands.spawn((
+        Sprite::from_image(asset_server.load("asteroid5.png")),
+        Transform::from_xyz(-200.0, 0.0, 0.0).with_scale(Vec3::splat(4.0)),
+        Marker,
+    ));
+    commands.spawn((
+        Sprite::from_image(asset_server.load("asteroid5.png")),
+        Transform::from_xyz(200.0, 0.0, 0.0).with_scale(Vec3::splat(4.0)),
+        RepeatingFlicker::builder()
+            .with_flicker_time_length(0.6)
+            .with_time_between_pulses(0.4)
+            .with_gradient(
+                FlickerGradient::new([
+                    Color::srgba(0.6, 0.9, 1.0, 0.8),
+                    Color::srgba(0.1, 0.2, 1.0, 0.5),
+                ])
+                .with_color_space(FlickerColorSpace::Hsva),
+            )
+            .build(),
+    ));
+}
+
+fn tick(query: Query<Entity, With<Marker>>, mut event_writer: MessageWriter<FlickerStartEvent>) {
+    for e in query.iter() {
+        event_writer.write(
+            FlickerStartEvent::builder(e)
+                .with_secs(FLICKER_LENGTH)
+                .with_gradient(
+                    FlickerGradient::new([
+                        Color::WHITE,
+                        Color::srgba(1.0, 0.1, 0.0, 0.8),
+                        Color::srgba(1.0, 0.1, 0.0, 0.0),
+                    ])
+                    .with_color_space(FlickerColorSpace::Oklaba),
+                )
+                .build(),
+        );
+    }
+}
@@ -0,0 +1,62 @@
+// Flickers a "character" built from several child sprites with a single event. The rightmost
+// part has NoFlicker, so it and its children are left alone.
+use bevy::prelude::*;
+use bevy_flicker::prelude::*;
+
+const FIXED_TIMESTEP: f64 = 1.0;
+const FLICKER_LENGTH: f32 = 0.5;
+
+#[derive(Component, Default)]
+pub struct Marker;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(FlickerPlugin)
+        .add_systems(Startup, setup)
+        .add_systems(FixedUpdate, tick)
+        .insert_resource(Time::<Fixed>::from_seconds(FIXED_TIMESTEP))
+        .run();
+}
+
+fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.spawn(Camera2d::default());
+    let image = asset_server.load("asteroid5.png");
+    commands.spawn((
+        Transform::default().with_scale(Vec3::splat(3.0)),
+        Visibility::default(),
+        Marker,
+        children![
+            (
+                Sprite::from_image(image.clone()),
+                Transform::from_xyz(-40.0, 0.0, 0.0),
+                children![(
+                    Sprite::from_image(image.clone()),
+                    Transform::from_xyz(0.0, 40.0, 0.0).with_scale(Vec3::splat(0.5)),
+                )],
+            ),
+            (Sprite::from_image(image.clone()), Transform::default()),
+            (
+                Sprite::from_image(image.clone()),
+                Transform::from_xyz(40.0, 0.0, 0.0),
+                NoFlicker,
+                children![(
+                    Sprite::from_image(image),
+                    Transform::from_xyz(0.0, 40.0, 0.0).with_scale(Vec3::splat(0.5)),
+                )],
+            ),
+        ],
+    ));
+}
+
+fn tick(query: Query<Entity, With<Marker>>, mut event_writer: MessageWriter<FlickerStartEvent>) {
+    for e in query.iter() {
+        event_writer.write(
+            FlickerStartEvent::builder(e)
+                .with_secs(FLICKER_LENGTH)
+                .with_color(LinearRgba::new(1.0, 1.0, 1.0, 0.8).into())
+                .with_recursive(true)
+                .build(),
+        );
+    }
+}
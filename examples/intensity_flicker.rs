@@ -0,0 +1,55 @@
+// Shows off the different intensity curves, from left to right: constant, fade out, ease in-out,
+// an attack/decay/sustain/release envelope, and a custom curve.
+use bevy::prelude::*;
+use bevy_flicker::prelude::*;
+
+const FIXED_TIMESTEP: f64 = 1.5;
+const FLICKER_LENGTH: f32 = 1.0;
+
+#[derive(Component)]
+pub struct Marker(FlickerIntensity);
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(FlickerPlugin)
+        .add_systems(Startup, setup)
+        .add_systems(FixedUpdate, tick)
+        .insert_resource(Time::<Fixed>::from_seconds(FIXED_TIMESTEP))
+        .run();
+}
+
+fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.spawn(Camera2d::default());
+    let intensities = [
+        FlickerIntensity::Constant,
+        FlickerIntensity::FadeOut,
+        FlickerIntensity::EaseInOut,
+        FlickerIntensity::Adsr {
+            attack: 0.1,
+            decay: 0.2,
+            sustain: 0.5,
+            release: 0.3,
+        },
+        FlickerIntensity::custom(|t| (t * 20.0).sin().abs()),
+    ];
+    for (i, intensity) in intensities.into_iter().enumerate() {
+        commands.spawn((
+            Sprite::from_image(asset_server.load("asteroid5.png")),
+            Transform::from_xyz(-400.0 + i as f32 * 200.0, 0.0, 0.0).with_scale(Vec3::splat(3.0)),
+            Marker(intensity),
+        ));
+    }
+}
+
+fn tick(query: Query<(Entity, &Marker)>, mut event_writer: MessageWriter<FlickerStartEvent>) {
+    for (e, marker) in query.iter() {
+        event_writer.write(
+            FlickerStartEvent::builder(e)
+                .with_secs(FLICKER_LENGTH)
+                .with_color(LinearRgba::new(1.0, 1.0, 1.0, 0.8).into())
+                .with_intensity(marker.0.clone())
+                .build(),
+        );
+    }
+}
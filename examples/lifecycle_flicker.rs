@@ -0,0 +1,80 @@
+// Logs the lifecycle of each flicker, once through an observer on the entity and once by reading
+// the messages. Flickers are sent faster than they finish, so most are interrupted by the next
+// one, except every third tick is skipped to let one run to the end.
+use bevy::prelude::*;
+use bevy_flicker::prelude::*;
+
+const FIXED_TIMESTEP: f64 = 0.5;
+const FLICKER_LENGTH: f32 = 0.75;
+
+#[derive(Component, Default)]
+pub struct Marker;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(FlickerPlugin)
+        .add_systems(Startup, setup)
+        .add_systems(FixedUpdate, tick)
+        .add_systems(Update, read_messages)
+        .insert_resource(Time::<Fixed>::from_seconds(FIXED_TIMESTEP))
+        .run();
+}
+
+fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.spawn(Camera2d::default());
+    commands
+        .spawn((
+            Sprite::from_image(asset_server.load("asteroid5.png")),
+            Transform::default().with_scale(Vec3::splat(4.0)),
+            Marker,
+        ))
+        .observe(|event: On<FlickerStarted>| {
+            info!("Observed start on {}, play a sound!", event.entity);
+        })
+        .observe(|event: On<FlickerEnded>| {
+            info!("Observed end on {}, invincibility is over", event.entity);
+        });
+}
+
+fn tick(
+    query: Query<Entity, With<Marker>>,
+    mut event_writer: MessageWriter<FlickerStartEvent>,
+    mut count: Local<u32>,
+) {
+    *count += 1;
+    if *count % 3 == 0 {
+        return;
+    }
+    for e in query.iter() {
+        event_writer.write(
+            FlickerStartEvent::builder(e)
+                .with_secs(FLICKER_LENGTH)
+                .with_color(LinearRgba::new(1.0, 1.0, 1.0, 0.6).into())
+                .build(),
+        );
+    }
+}
+
+fn read_messages(
+    mut started: MessageReader<FlickerStarted>,
+    mut ended: MessageReader<FlickerEnded>,
+    mut interrupted: MessageReader<FlickerInterrupted>,
+) {
+    for message in started.read() {
+        info!(
+            "{} started a {}s flicker",
+            message.entity, message.flicker.secs
+        );
+    }
+    for message in ended.read() {
+        info!("{} finished flickering", message.entity);
+    }
+    for message in interrupted.read() {
+        info!(
+            "{} was interrupted {:.0}% through its flicker",
+            message.entity,
+            message.progress * 100.0
+        );
+    }
+}
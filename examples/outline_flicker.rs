@@ -0,0 +1,28 @@
+// Outlines the asteroids instead of filling them, such as to show which one is selected. The
+// outline on the right is thicker and uses a repeating flicker.
+use bevy::prelude::*;
+use bevy_flicker::prelude::*;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(FlickerPlugin)
+        .add_systems(Startup, setup)
+        .run();
+}
+
+fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.spawn(Camera2d::default());
+    for (x, thickness) in [(-150.0, 2.0), (150.0, 6.0)] {
+        commands.spawn((
+            Sprite::from_image(asset_server.load("asteroid_round.png")),
+            Transform::from_xyz(x, 0.0, 0.0),
+            RepeatingFlicker::builder()
+                .with_color(Color::srgb(1.0, 0.9, 0.2))
+                .with_style(FlickerStyle::Outline { thickness })
+                .with_flicker_time_length(0.4)
+                .with_time_between_pulses(0.6)
+                .build(),
+        ));
+    }
+}
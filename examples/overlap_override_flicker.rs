@@ -0,0 +1,63 @@
+// The left sprite uses the global overlap action, which is set to ignore, so it only flickers
+// every other second. The middle sprite overrides it with FlickerOverlapOverride to stack its
+// flickers, and the right sprite's events override both to extend the active flicker.
+use bevy::prelude::*;
+use bevy_flicker::prelude::*;
+
+const FIXED_TIMESTEP: f64 = 0.5;
+const FLICKER_LENGTH: f32 = 0.8;
+
+#[derive(Component)]
+pub struct Marker;
+
+#[derive(Component)]
+pub struct ExtendMarker;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(FlickerPlugin)
+        .insert_resource(FlickerPluginConfig {
+            overlap_action: FlickerOverlapAction::Ignore,
+        })
+        .add_systems(Startup, setup)
+        .add_systems(FixedUpdate, tick)
+        .insert_resource(Time::<Fixed>::from_seconds(FIXED_TIMESTEP))
+        .run();
+}
+
+fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.spawn(Camera2d::default());
+    commands.spawn((
+        Sprite::from_image(asset_server.load("asteroid5.png")),
+        Transform::from_xyz(-200.0, 0.0, 0.0).with_scale(Vec3::splat(3.0)),
+        Marker,
+    ));
+    commands.spawn((
+        Sprite::from_image(asset_server.load("asteroid5.png")),
+        Transform::from_xyz(0.0, 0.0, 0.0).with_scale(Vec3::splat(3.0)),
+        FlickerOverlapOverride(FlickerOverlapAction::Stack),
+        Marker,
+    ));
+    commands.spawn((
+        Sprite::from_image(asset_server.load("asteroid5.png")),
+        Transform::from_xyz(200.0, 0.0, 0.0).with_scale(Vec3::splat(3.0)),
+        Marker,
+        ExtendMarker,
+    ));
+}
+
+fn tick(
+    query: Query<(Entity, Has<ExtendMarker>), With<Marker>>,
+    mut event_writer: MessageWriter<FlickerStartEvent>,
+) {
+    for (e, extend) in query.iter() {
+        let mut builder = FlickerStartEvent::builder(e)
+            .with_secs(FLICKER_LENGTH)
+            .with_color(Color::srgba(0.2, 0.4, 1.0, 0.4));
+        if extend {
+            builder = builder.with_overlap_action(FlickerOverlapAction::Extend);
+        }
+        event_writer.write(builder.build());
+    }
+}
@@ -0,0 +1,52 @@
+// Press space to freeze the asteroid on the right, as with a time stop ability, and again to let
+// it go. Its flicker holds at whatever color it was at while the one on the left keeps going.
+use bevy::prelude::*;
+use bevy_flicker::prelude::*;
+
+#[derive(Component)]
+struct Freezable;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(FlickerPlugin)
+        .add_systems(Startup, setup)
+        .add_systems(Update, toggle_freeze)
+        .run();
+}
+
+fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.spawn(Camera2d::default());
+    for x in [-200.0, 200.0] {
+        let mut entity_commands = commands.spawn((
+            Sprite::from_image(asset_server.load("asteroid5.png")),
+            Transform::from_xyz(x, 0.0, 0.0).with_scale(Vec3::splat(4.0)),
+            RepeatingFlicker::builder()
+                .with_color(Color::srgba(0.3, 0.6, 1.0, 0.9))
+                .with_intensity(FlickerIntensity::FadeOut)
+                .with_flicker_time_length(1.0)
+                .with_time_between_pulses(0.5)
+                .build(),
+        ));
+        if x > 0.0 {
+            entity_commands.insert(Freezable);
+        }
+    }
+}
+
+fn toggle_freeze(
+    keys: Res<ButtonInput<KeyCode>>,
+    freezable: Query<(Entity, Has<FlickerPaused>), With<Freezable>>,
+    mut commands: Commands,
+) {
+    if !keys.just_pressed(KeyCode::Space) {
+        return;
+    }
+    for (entity, paused) in freezable.iter() {
+        if paused {
+            commands.entity(entity).resume_flicker();
+        } else {
+            commands.entity(entity).pause_flicker();
+        }
+    }
+}
@@ -0,0 +1,38 @@
+// A grid of pickups that flicker forever. Each one keeps a single overlay alive with
+// FlickerOverlay rather than spawning and despawning one for every flicker.
+use bevy::prelude::*;
+use bevy_flicker::prelude::*;
+
+const GRID_SIZE: i32 = 10;
+const SPACING: f32 = 64.0;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(FlickerPlugin)
+        .add_systems(Startup, setup)
+        .run();
+}
+
+fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.spawn(Camera2d::default());
+    let offset = (GRID_SIZE - 1) as f32 * SPACING / 2.0;
+    for x in 0..GRID_SIZE {
+        for y in 0..GRID_SIZE {
+            commands.spawn((
+                Sprite::from_image(asset_server.load("asteroid5.png")),
+                Transform::from_xyz(
+                    x as f32 * SPACING - offset,
+                    y as f32 * SPACING - offset,
+                    0.0,
+                ),
+                FlickerOverlay::default(),
+                RepeatingFlicker::builder()
+                    .with_color(LinearRgba::new(1.0, 1.0, 0.4, 0.6).into())
+                    .with_flicker_time_length(0.2)
+                    .with_time_between_pulses(0.3 + (x + y) as f32 * 0.02)
+                    .build(),
+            ));
+        }
+    }
+}
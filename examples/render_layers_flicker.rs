@@ -0,0 +1,52 @@
+// The camera only draws render layer 1, the overlay copies the layer of the flickered sprite so it
+// is drawn by the same camera. The sword sits half a unit in front of the asteroid, the z offset
+// of the flicker is lowered so that the overlay is placed between the two and the sword stays on
+// top.
+use bevy::{camera::visibility::RenderLayers, prelude::*};
+use bevy_flicker::prelude::*;
+
+const FIXED_TIMESTEP: f64 = 1.0;
+const FLICKER_LENGTH: f32 = 0.5;
+
+#[derive(Component, Default)]
+pub struct Marker;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(FlickerPlugin)
+        .insert_resource(FlickerPluginConfig {
+            z_offset: 0.25,
+            ..default()
+        })
+        .add_systems(Startup, setup)
+        .add_systems(FixedUpdate, tick)
+        .insert_resource(Time::<Fixed>::from_seconds(FIXED_TIMESTEP))
+        .run();
+}
+
+fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.spawn((Camera2d::default(), RenderLayers::layer(1)));
+    commands
+        .spawn((
+            Sprite::from_image(asset_server.load("asteroid_round.png")),
+            RenderLayers::layer(1),
+            Marker,
+        ))
+        .with_child((
+            Sprite::from_color(Color::srgb(0.7, 0.7, 0.8), Vec2::new(12.0, 96.0)),
+            Transform::from_xyz(32.0, 0.0, 0.5),
+            RenderLayers::layer(1),
+        ));
+}
+
+fn tick(query: Query<Entity, With<Marker>>, mut event_writer: MessageWriter<FlickerStartEvent>) {
+    for e in query.iter() {
+        event_writer.write(
+            FlickerStartEvent::builder(e)
+                .with_secs(FLICKER_LENGTH)
+                .with_color(LinearRgba::new(1.0, 0.0, 0.0, 0.6).into())
+                .build(),
+        );
+    }
+}
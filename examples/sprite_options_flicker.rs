@@ -0,0 +1,90 @@
+// Sprites drawn with different options, the overlay covers each of them exactly. From left to
+// right: flipped, anchored to its bottom left corner, drawing only part of its image, sliced and
+// tiled.
+use bevy::prelude::*;
+use bevy_flicker::prelude::*;
+
+const FIXED_TIMESTEP: f64 = 1.0;
+const FLICKER_LENGTH: f32 = 0.5;
+
+#[derive(Component)]
+pub struct Marker;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(FlickerPlugin)
+        .add_systems(Startup, setup)
+        .add_systems(FixedUpdate, tick)
+        .insert_resource(Time::<Fixed>::from_seconds(FIXED_TIMESTEP))
+        .run();
+}
+
+fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.spawn(Camera2d::default());
+    let image = asset_server.load("asteroid_sheet_test.png");
+    let sprites = [
+        (
+            Sprite {
+                image: image.clone(),
+                flip_x: true,
+                flip_y: true,
+                ..default()
+            },
+            Anchor::CENTER,
+        ),
+        (Sprite::from_image(image.clone()), Anchor::BOTTOM_LEFT),
+        (
+            Sprite {
+                image: image.clone(),
+                rect: Some(Rect::new(0.0, 64.0, 64.0, 160.0)),
+                ..default()
+            },
+            Anchor::CENTER,
+        ),
+        (
+            Sprite {
+                image: image.clone(),
+                custom_size: Some(Vec2::new(160.0, 320.0)),
+                image_mode: SpriteImageMode::Sliced(TextureSlicer {
+                    border: BorderRect::all(16.0),
+                    ..default()
+                }),
+                ..default()
+            },
+            Anchor::CENTER,
+        ),
+        (
+            Sprite {
+                image: image.clone(),
+                custom_size: Some(Vec2::new(160.0, 320.0)),
+                image_mode: SpriteImageMode::Tiled {
+                    tile_x: true,
+                    tile_y: true,
+                    stretch_value: 0.5,
+                },
+                ..default()
+            },
+            Anchor::CENTER,
+        ),
+    ];
+    for (i, (sprite, anchor)) in sprites.into_iter().enumerate() {
+        commands.spawn((
+            sprite,
+            anchor,
+            Transform::from_xyz(-500.0 + i as f32 * 220.0, 0.0, 0.0),
+            Marker,
+        ));
+    }
+}
+
+fn tick(query: Query<Entity, With<Marker>>, mut event_writer: MessageWriter<FlickerStartEvent>) {
+    for e in query.iter() {
+        event_writer.write(
+            FlickerStartEvent::builder(e)
+                .with_secs(FLICKER_LENGTH)
+                .with_color(LinearRgba::new(0.2, 1.0, 0.2, 0.6).into())
+                .build(),
+        );
+    }
+}
@@ -0,0 +1,52 @@
+// Press space to stop all flickers instantly, or F to fade them out over half a second. Stopping
+// also removes the RepeatingFlicker, which would otherwise keep starting new flickers.
+use bevy::prelude::*;
+use bevy_flicker::prelude::*;
+
+const FADE_LENGTH: f32 = 0.5;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(FlickerPlugin)
+        .add_systems(Startup, setup)
+        .add_systems(Update, stop)
+        .run();
+}
+
+fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.spawn(Camera2d::default());
+    for x in [-200.0, 0.0, 200.0] {
+        commands.spawn((
+            Sprite::from_image(asset_server.load("asteroid5.png")),
+            Transform::from_xyz(x, 0.0, 0.0).with_scale(Vec3::splat(3.0)),
+            RepeatingFlicker::builder()
+                .with_color(LinearRgba::new(1.0, 0.0, 0.0, 0.6).into())
+                .with_flicker_time_length(2.0)
+                .with_time_between_pulses(0.5)
+                .build(),
+        ));
+    }
+}
+
+fn stop(
+    keys: Res<ButtonInput<KeyCode>>,
+    flickering: Query<Entity, With<RepeatingFlicker>>,
+    mut commands: Commands,
+) {
+    let fade = if keys.just_pressed(KeyCode::Space) {
+        None
+    } else if keys.just_pressed(KeyCode::KeyF) {
+        Some(FADE_LENGTH)
+    } else {
+        return;
+    };
+    for entity in flickering.iter() {
+        let mut entity_commands = commands.entity(entity);
+        entity_commands.remove::<RepeatingFlicker>();
+        match fade {
+            Some(fade_secs) => entity_commands.stop_flicker_with_fade(fade_secs),
+            None => entity_commands.stop_flicker(),
+        };
+    }
+}
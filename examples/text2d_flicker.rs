@@ -0,0 +1,101 @@
+// Flickering world-space text, requires the "text" feature. Press space to hit the asteroid, which
+// flashes along with its name plate and spawns a damage number that flashes as it floats away.
+// The damage number is not flickered recursively, its " CRIT" span is tinted along with it anyway.
+// Hits are ordered before the FlickerSet so the damage number exists by the time it is flickered.
+use bevy::prelude::*;
+use bevy_flicker::prelude::*;
+
+#[derive(Component)]
+struct Target;
+
+#[derive(Component)]
+struct DamageNumber;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(FlickerPlugin)
+        .add_systems(Startup, setup)
+        .add_systems(Update, (hit.before(FlickerSet), float))
+        .run();
+}
+
+fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.spawn(Camera2d::default());
+    commands
+        .spawn((
+            Target,
+            Sprite::from_image(asset_server.load("asteroid5.png")),
+            Transform::from_scale(Vec3::splat(4.0)),
+        ))
+        .with_children(|parent| {
+            // The name plate is made of two sections, each of which is tinted separately
+            parent
+                .spawn((
+                    Text2d::new("Asteroid "),
+                    TextFont::from_font_size(8.0),
+                    TextColor(Color::WHITE),
+                    Transform::from_xyz(0.0, 24.0, 0.0),
+                ))
+                .with_child((
+                    TextSpan::new("Lv. 3"),
+                    TextFont::from_font_size(8.0),
+                    TextColor(Color::srgb(0.4, 0.8, 1.0)),
+                ));
+        });
+}
+
+fn hit(
+    keys: Res<ButtonInput<KeyCode>>,
+    targets: Query<Entity, With<Target>>,
+    mut event_writer: MessageWriter<FlickerStartEvent>,
+    mut commands: Commands,
+) {
+    if !keys.just_pressed(KeyCode::Space) {
+        return;
+    }
+    for entity in targets.iter() {
+        event_writer.write(
+            FlickerStartEvent::builder(entity)
+                .with_secs(0.2)
+                .with_color(Color::srgba(1.0, 0.2, 0.2, 0.8))
+                .with_recursive(true)
+                .build(),
+        );
+    }
+
+    let damage_number = commands
+        .spawn((
+            DamageNumber,
+            Text2d::new("-12"),
+            TextFont::from_font_size(32.0),
+            TextColor(Color::srgb(1.0, 0.9, 0.2)),
+            Transform::from_xyz(40.0, 40.0, 1.0),
+        ))
+        .with_child((
+            TextSpan::new(" CRIT"),
+            TextFont::from_font_size(24.0),
+            TextColor(Color::srgb(1.0, 0.4, 0.1)),
+        ))
+        .id();
+    event_writer.write(
+        FlickerStartEvent::builder(damage_number)
+            .with_secs(0.5)
+            .with_color(Color::WHITE)
+            .with_intensity(FlickerIntensity::FadeOut)
+            .build(),
+    );
+}
+
+fn float(
+    mut damage_numbers: Query<(Entity, &mut Transform), With<DamageNumber>>,
+    time: Res<Time>,
+    mut commands: Commands,
+) {
+    for (entity, mut transform) in damage_numbers.iter_mut() {
+        transform.translation.y += 60.0 * time.delta_secs();
+        if transform.translation.y > 160.0 {
+            commands.entity(entity).despawn();
+        }
+    }
+}
@@ -0,0 +1,64 @@
+// Flickers advancing with different clocks. Press P to pause the game and S to toggle slow
+// motion. The left asteroid uses virtual time and freezes or slows with the game, the middle one
+// uses real time like a UI element would and keeps going, and the right one is slowed down on its
+// own with a time scale.
+use bevy::prelude::*;
+use bevy_flicker::prelude::*;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(FlickerPlugin)
+        .add_systems(Startup, setup)
+        .add_systems(Update, control_time)
+        .run();
+}
+
+fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.spawn(Camera2d::default());
+    let repeating_flicker = || {
+        RepeatingFlicker::builder()
+            .with_color(Color::srgba(1.0, 1.0, 1.0, 0.8))
+            .with_flicker_time_length(0.3)
+            .with_time_between_pulses(0.5)
+            .build()
+    };
+    let sprite = Sprite::from_image(asset_server.load("asteroid5.png"));
+    let transform = Transform::from_scale(Vec3::splat(4.0));
+
+    commands.spawn((
+        sprite.clone(),
+        transform.with_translation(Vec3::new(-256.0, 0.0, 0.0)),
+        repeating_flicker(),
+    ));
+    commands.spawn((
+        sprite.clone(),
+        transform,
+        repeating_flicker(),
+        FlickerTimeSourceOverride(FlickerTimeSource::Real),
+    ));
+    commands.spawn((
+        sprite,
+        transform.with_translation(Vec3::new(256.0, 0.0, 0.0)),
+        repeating_flicker(),
+        FlickerTimeScale(0.25),
+    ));
+}
+
+fn control_time(keys: Res<ButtonInput<KeyCode>>, mut time: ResMut<Time<Virtual>>) {
+    if keys.just_pressed(KeyCode::KeyP) {
+        if time.is_paused() {
+            time.unpause();
+        } else {
+            time.pause();
+        }
+    }
+    if keys.just_pressed(KeyCode::KeyS) {
+        let speed = if time.relative_speed() < 1.0 {
+            1.0
+        } else {
+            0.2
+        };
+        time.set_relative_speed(speed);
+    }
+}
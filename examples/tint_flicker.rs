@@ -0,0 +1,66 @@
+// Flickers that tint the entity itself instead of spawning an overlay. The asteroid on the left
+// has a sprite sitting just in front of it, which an overlay at z + 1.0 would be drawn over, while
+// the tint leaves it alone. The two squares on the right share a ColorMaterial, only the flickered
+// one changes color.
+use bevy::prelude::*;
+use bevy_flicker::prelude::*;
+
+const FIXED_TIMESTEP: f64 = 1.0;
+const FLICKER_LENGTH: f32 = 0.5;
+
+#[derive(Component, Default)]
+pub struct Marker;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(FlickerPlugin)
+        .add_systems(Startup, setup)
+        .add_systems(FixedUpdate, tick)
+        .insert_resource(Time::<Fixed>::from_seconds(FIXED_TIMESTEP))
+        .run();
+}
+
+fn setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    commands.spawn(Camera2d::default());
+    commands.spawn((
+        Sprite::from_image(asset_server.load("asteroid_round.png")),
+        Transform::from_xyz(-200.0, 0.0, 0.0),
+        Marker,
+    ));
+    commands.spawn((
+        Sprite::from_color(Color::srgb(0.2, 0.8, 0.2), Vec2::splat(48.0)),
+        Transform::from_xyz(-200.0, 0.0, 0.5),
+    ));
+
+    let mesh = meshes.add(Mesh::from(Rectangle::default()));
+    let material = materials.add(ColorMaterial::from(Color::from(LinearRgba::RED)));
+    commands.spawn((
+        Mesh2d(mesh.clone()),
+        MeshMaterial2d(material.clone()),
+        Transform::from_xyz(100.0, 0.0, 0.0).with_scale(Vec3::splat(128.0)),
+        Marker,
+    ));
+    commands.spawn((
+        Mesh2d(mesh),
+        MeshMaterial2d(material),
+        Transform::from_xyz(300.0, 0.0, 0.0).with_scale(Vec3::splat(128.0)),
+    ));
+}
+
+fn tick(query: Query<Entity, With<Marker>>, mut event_writer: MessageWriter<FlickerStartEvent>) {
+    for e in query.iter() {
+        event_writer.write(
+            FlickerStartEvent::builder(e)
+                .with_secs(FLICKER_LENGTH)
+                .with_color(LinearRgba::new(0.0, 0.0, 1.0, 0.8).into())
+                .with_mode(FlickerMode::Tint)
+                .build(),
+        );
+    }
+}
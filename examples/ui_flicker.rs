@@ -0,0 +1,126 @@
+// Flickering UI nodes, requires the "ui" feature. Press space to damage the health bar, which
+// flashes along with its fill and the damage number. The inventory slot is a button that flickers
+// on its own to show a new item was picked up, and can still be hovered and clicked while it
+// flashes.
+use bevy::prelude::*;
+use bevy_flicker::prelude::*;
+
+#[derive(Component)]
+struct HealthBar;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(FlickerPlugin)
+        .add_systems(Startup, setup)
+        .add_systems(Update, (damage, inventory_slot))
+        .run();
+}
+
+fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.spawn(Camera2d::default());
+    commands
+        .spawn(Node {
+            width: percent(100),
+            height: percent(100),
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::Center,
+            justify_content: JustifyContent::Center,
+            row_gap: px(32),
+            ..default()
+        })
+        .with_children(|parent| {
+            parent
+                .spawn((
+                    HealthBar,
+                    Node {
+                        width: px(300),
+                        height: px(32),
+                        border: UiRect::all(px(4)),
+                        border_radius: BorderRadius::all(px(8)),
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgb(0.2, 0.05, 0.05)),
+                    BorderColor::all(Color::WHITE),
+                ))
+                .with_children(|bar| {
+                    bar.spawn((
+                        Node {
+                            width: percent(70),
+                            height: percent(100),
+                            border_radius: BorderRadius::all(px(4)),
+                            ..default()
+                        },
+                        BackgroundColor(Color::srgb(0.8, 0.1, 0.1)),
+                    ));
+                });
+            parent.spawn((
+                Text::new("-12"),
+                TextFont::from_font_size(32.0),
+                TextColor(Color::srgb(1.0, 0.9, 0.2)),
+            ));
+            parent.spawn((
+                Button,
+                Node {
+                    width: px(96),
+                    height: px(96),
+                    border: UiRect::all(px(2)),
+                    ..default()
+                },
+                BorderColor::all(Color::srgb(0.6, 0.6, 0.6)),
+                ImageNode::new(asset_server.load("asteroid5.png")),
+                RepeatingFlicker::builder()
+                    .with_color(LinearRgba::new(1.0, 1.0, 0.4, 0.6).into())
+                    .with_flicker_time_length(0.2)
+                    .with_time_between_pulses(1.0)
+                    .build(),
+            ));
+        });
+}
+
+fn damage(
+    keys: Res<ButtonInput<KeyCode>>,
+    health_bars: Query<Entity, With<HealthBar>>,
+    texts: Query<Entity, With<Text>>,
+    mut event_writer: MessageWriter<FlickerStartEvent>,
+) {
+    if !keys.just_pressed(KeyCode::Space) {
+        return;
+    }
+    for entity in health_bars.iter() {
+        // Recursive so the fill inside of the bar flashes too
+        event_writer.write(
+            FlickerStartEvent::builder(entity)
+                .with_secs(0.3)
+                .with_color(Color::WHITE.with_alpha(0.8))
+                .with_recursive(true)
+                .build(),
+        );
+    }
+    for entity in texts.iter() {
+        // Text has nothing to draw an overlay over, so its color is tinted instead
+        event_writer.write(
+            FlickerStartEvent::builder(entity)
+                .with_secs(0.3)
+                .with_color(Color::WHITE)
+                .with_intensity(FlickerIntensity::FadeOut)
+                .build(),
+        );
+    }
+}
+
+fn inventory_slot(
+    mut slots: Query<(&Interaction, &mut BorderColor), (Changed<Interaction>, With<Button>)>,
+) {
+    for (interaction, mut border_color) in slots.iter_mut() {
+        let color = match interaction {
+            Interaction::Pressed => {
+                info!("Used the item");
+                Color::srgb(1.0, 0.9, 0.2)
+            }
+            Interaction::Hovered => Color::WHITE,
+            Interaction::None => Color::srgb(0.6, 0.6, 0.6),
+        };
+        *border_color = BorderColor::all(color);
+    }
+}
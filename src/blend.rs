@@ -0,0 +1,64 @@
+use bevy_reflect::Reflect;
+
+use bevy_color::{Alpha, ColorToComponents, LinearRgba};
+use bevy_math::Vec3;
+
+/// How the flicker color is combined with the underlying sprite.
+///
+/// The result of the blend is drawn over the sprite using the alpha of the flicker color, so an
+/// alpha of 1.0 shows only the blended result while lower alphas let more of the sprite through.
+///
+/// Mesh flickers have no underlying texture to sample, so they blend against plain white.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Reflect)]
+#[repr(u32)]
+pub enum FlickerBlendMode {
+    /// Replaces the sprite with the flicker color.
+    #[default]
+    Replace = 0,
+
+    /// Multiplies the sprite with the flicker color, darkening it.
+    Multiply = 1,
+
+    /// Adds the flicker color to the sprite, brightening it.
+    Add = 2,
+
+    /// The inverse of multiplying the inverted colors, brightens without blowing out as quickly
+    /// as [FlickerBlendMode::Add].
+    Screen = 3,
+
+    /// Multiplies the darker parts of the sprite and screens the lighter parts, increasing
+    /// contrast.
+    Overlay = 4,
+
+    /// Recolors the sprite with the flicker color while keeping the luminance of the sprite, so
+    /// its shading stays visible.
+    Tint = 5,
+}
+
+impl FlickerBlendMode {
+    /// The value the shader uses to identify this blend mode
+    pub(crate) fn shader_index(self) -> u32 {
+        self as u32
+    }
+
+    /// Draws the flicker color over the base color the same way the shaders do, for flickers
+    /// that recolor their target instead of drawing an overlay. The alpha of the base color is
+    /// kept as is.
+    pub(crate) fn apply(self, base: LinearRgba, flicker: LinearRgba) -> LinearRgba {
+        let luminance = |color: Vec3| color.dot(Vec3::new(0.2126, 0.7152, 0.0722));
+        let (b, f) = (base.to_vec3(), flicker.to_vec3());
+        let blended = match self {
+            Self::Replace => f,
+            Self::Multiply => b * f,
+            Self::Add => (b + f).min(Vec3::ONE),
+            Self::Screen => Vec3::ONE - (Vec3::ONE - b) * (Vec3::ONE - f),
+            Self::Overlay => {
+                let low = 2.0 * b * f;
+                let high = Vec3::ONE - 2.0 * (Vec3::ONE - b) * (Vec3::ONE - f);
+                Vec3::select(b.cmplt(Vec3::splat(0.5)), low, high)
+            }
+            Self::Tint => f * (luminance(b) / luminance(f).max(0.0001)),
+        };
+        LinearRgba::from_vec3(b.lerp(blended, flicker.alpha)).with_alpha(base.alpha)
+    }
+}
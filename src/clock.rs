@@ -0,0 +1,48 @@
+use crate::{
+    components::{FlickerPaused, FlickerTimeScale, FlickerTimeSourceOverride},
+    config::{FlickerPluginConfig, FlickerTimeSource},
+};
+
+use bevy_ecs::{
+    entity::Entity,
+    query::Has,
+    system::{Query, Res, SystemParam},
+};
+use bevy_time::{Real, Time};
+
+use std::time::Duration;
+
+/// The clocks that flickers advance with, see [FlickerTimeSource]
+#[derive(SystemParam)]
+pub(crate) struct FlickerClock<'w, 's> {
+    time: Res<'w, Time>,
+    real_time: Res<'w, Time<Real>>,
+    config: Res<'w, FlickerPluginConfig>,
+    overrides: Query<
+        'w,
+        's,
+        (
+            Option<&'static FlickerTimeSourceOverride>,
+            Option<&'static FlickerTimeScale>,
+            Has<FlickerPaused>,
+        ),
+    >,
+}
+
+impl FlickerClock<'_, '_> {
+    /// How far the flickers of the given entity advance this frame
+    pub(crate) fn delta(&self, entity: Entity) -> Duration {
+        let (source, scale, paused) = self.overrides.get(entity).unwrap_or_default();
+        if paused {
+            return Duration::ZERO;
+        }
+        let delta = match source.map_or(self.config.time_source, |source| source.0) {
+            FlickerTimeSource::Virtual => self.time.delta(),
+            FlickerTimeSource::Real => self.real_time.delta(),
+        };
+        match scale {
+            Some(scale) => delta.mul_f32(scale.0.max(0.0)),
+            None => delta,
+        }
+    }
+}
@@ -0,0 +1,41 @@
+use bevy_ecs::system::EntityCommands;
+
+use crate::{components::FlickerPaused, events::FlickerStopEvent};
+
+/// Adds flicker controls to [EntityCommands]
+pub trait FlickerCommandsExt {
+    /// Stops the active flicker on this entity instantly, see [FlickerStopEvent]
+    fn stop_flicker(&mut self) -> &mut Self;
+
+    /// Stops the active flicker on this entity, fading it out over the given number of seconds,
+    /// see [FlickerStopEvent]
+    fn stop_flicker_with_fade(&mut self, fade_secs: f32) -> &mut Self;
+
+    /// Freezes the flickers on this entity where they are, see [FlickerPaused]
+    fn pause_flicker(&mut self) -> &mut Self;
+
+    /// Lets the flickers on this entity continue after [FlickerCommandsExt::pause_flicker]
+    fn resume_flicker(&mut self) -> &mut Self;
+}
+
+impl FlickerCommandsExt for EntityCommands<'_> {
+    fn stop_flicker(&mut self) -> &mut Self {
+        let event = FlickerStopEvent::new(self.id());
+        self.commands().write_message(event);
+        self
+    }
+
+    fn stop_flicker_with_fade(&mut self, fade_secs: f32) -> &mut Self {
+        let event = FlickerStopEvent::with_fade(self.id(), fade_secs);
+        self.commands().write_message(event);
+        self
+    }
+
+    fn pause_flicker(&mut self) -> &mut Self {
+        self.insert(FlickerPaused)
+    }
+
+    fn resume_flicker(&mut self) -> &mut Self {
+        self.remove::<FlickerPaused>()
+    }
+}
@@ -1,29 +1,231 @@
-use crate::events::FlickerStartEvent;
+use crate::{
+    blend::FlickerBlendMode,
+    config::{FlickerOverlapAction, FlickerTimeSource},
+    events::{FlickerInterrupted, FlickerStartEvent, FlickerStartEventBuilder},
+    gradient::FlickerGradient,
+    intensity::FlickerIntensity,
+    mode::FlickerMode,
+    style::FlickerStyle,
+};
 
-use bevy_ecs::{component::Component, entity::Entity, reflect::ReflectComponent};
+use bevy_ecs::{
+    component::Component, entity::Entity, hierarchy::Children, lifecycle::HookContext,
+    reflect::ReflectComponent, world::DeferredWorld,
+};
 
-use bevy_reflect::Reflect;
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
 
 use bevy_time::{Timer, TimerMode};
 
-use bevy_color::Color;
+use std::{collections::VecDeque, time::Duration};
 
-#[derive(Component, Reflect)]
-pub struct Flickered(pub Timer);
+use bevy_color::{Alpha, Color, LinearRgba};
 
-impl Default for Flickered {
+/// Placed on the overlay entity of an active flicker, tracks how long the flicker has left and
+/// what it should look like as it progresses.
+#[derive(Component, Clone, Reflect)]
+#[reflect(Component)]
+pub struct Flickered {
+    pub(crate) timer: Timer,
+    pub(crate) event: FlickerStartEvent,
+
+    /// Set once the flicker has been stopped, fades out from the color it was stopped at
+    pub(crate) fade_out: Option<(Timer, LinearRgba)>,
+
+    /// How far up a stack of flickers the overlay is drawn, 0 for the bottom
+    pub(crate) layer: usize,
+}
+
+impl Flickered {
+    pub(crate) fn from_event(event: &FlickerStartEvent, layer: usize) -> Self {
+        Self {
+            timer: Timer::from_seconds(event.secs, TimerMode::Once),
+            event: event.clone(),
+            fade_out: None,
+            layer,
+        }
+    }
+
+    /// The event that started this flicker
+    pub fn event(&self) -> &FlickerStartEvent {
+        &self.event
+    }
+
+    /// How far along the flicker is, between 0.0 and 1.0. Stays where it is while stopping.
+    pub(crate) fn progress(&self) -> f32 {
+        self.timer.fraction()
+    }
+
+    /// Whether the flicker has been stopped and is fading out
+    pub(crate) fn is_stopping(&self) -> bool {
+        self.fade_out.is_some()
+    }
+
+    /// The number of seconds until the flicker finishes
+    pub(crate) fn remaining_secs(&self) -> f32 {
+        self.timer.remaining_secs()
+    }
+
+    /// Lengthens the flicker by the given number of seconds
+    pub(crate) fn extend(&mut self, secs: f32) {
+        let duration = self.timer.duration() + Duration::from_secs_f32(secs.max(0.0));
+        self.timer.set_duration(duration);
+    }
+
+    /// Begins fading the flicker out from its current color
+    pub(crate) fn stop(&mut self, fade_secs: f32) {
+        self.fade_out = Some((
+            Timer::from_seconds(fade_secs, TimerMode::Once),
+            self.current_color(),
+        ));
+    }
+
+    /// Advances the flicker, or its fade out once it has been stopped
+    pub(crate) fn tick(&mut self, delta: Duration) {
+        match self.fade_out.as_mut() {
+            Some((timer, _)) => timer.tick(delta),
+            None => self.timer.tick(delta),
+        };
+    }
+
+    /// Whether the flicker, or its fade out, has finished
+    pub(crate) fn is_finished(&self) -> bool {
+        match self.fade_out.as_ref() {
+            Some((timer, _)) => timer.is_finished(),
+            None => self.timer.is_finished(),
+        }
+    }
+
+    /// The color of the overlay at the current point in the flicker
+    pub(crate) fn current_color(&self) -> LinearRgba {
+        if let Some((timer, color)) = self.fade_out.as_ref() {
+            return color.with_alpha(color.alpha() * timer.fraction_remaining());
+        }
+        let progress = self.progress();
+        let color = self
+            .event
+            .gradient
+            .as_ref()
+            .and_then(|gradient| gradient.sample(progress))
+            .unwrap_or(self.event.color.into());
+        color.with_alpha(color.alpha() * self.event.intensity.sample(progress))
+    }
+}
+
+/// Placed on the overlay entities of descendants flickered by a recursive flicker, points at the
+/// overlay entity holding the [Flickered] timer shared by the whole group.
+#[derive(Component, Clone, Debug, Reflect)]
+#[reflect(Component)]
+#[relationship(relationship_target = FlickerGroup)]
+pub struct FlickerGroupMember(pub(crate) Entity);
+
+/// Placed alongside [Flickered] when the flicker was recursive, lists the overlay entities of the
+/// flickered descendants. They are despawned together with this entity.
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+#[relationship_target(relationship = FlickerGroupMember, linked_spawn)]
+pub struct FlickerGroup(Vec<Entity>);
+
+/// Overrides the global [FlickerPluginConfig::overlap_action][crate::config::FlickerPluginConfig]
+/// for flickers on this entity. An overlap action set on the flicker event itself still takes
+/// precedence.
+#[derive(Component, Clone, Copy, Debug, Default, Reflect)]
+#[reflect(Component)]
+pub struct FlickerOverlapOverride(pub FlickerOverlapAction);
+
+/// Overrides the global [FlickerPluginConfig::time_source][crate::config::FlickerPluginConfig]
+/// for flickers and [RepeatingFlicker] on this entity.
+#[derive(Component, Clone, Copy, Debug, Default, Reflect)]
+#[reflect(Component)]
+pub struct FlickerTimeSourceOverride(pub FlickerTimeSource);
+
+/// Scales how fast flickers and [RepeatingFlicker] advance on this entity, on top of the clock
+/// they use. 0.5 plays them at half speed and 0.0 holds them in place, negative values are
+/// treated as 0.0.
+#[derive(Component, Clone, Copy, Debug, Reflect)]
+#[reflect(Component)]
+pub struct FlickerTimeScale(pub f32);
+
+impl Default for FlickerTimeScale {
     fn default() -> Self {
-        Self(Timer::from_seconds(0.1, TimerMode::Once))
+        Self(1.0)
     }
 }
 
-#[allow(dead_code)]
-impl Flickered {
-    pub fn with_secs(secs: f32) -> Self {
-        Self(Timer::from_seconds(secs, TimerMode::Once))
+/// Freezes the flickers and [RepeatingFlicker] on this entity where they are until it is
+/// removed, without touching the clock they use. The flicker stays drawn at its current color,
+/// and recursive flickers freeze along with the entity they were started on.
+///
+/// Flickers started on a paused entity begin frozen at their start. See
+/// [FlickerCommandsExt::pause_flicker][crate::commands::FlickerCommandsExt::pause_flicker].
+#[derive(Component, Clone, Copy, Debug, Default, Reflect)]
+#[reflect(Component)]
+pub struct FlickerPaused;
+
+/// Keeps a single overlay entity alive on this entity between flickers, which is hidden when no
+/// flicker is active and shown again with an updated material for the next one. This avoids
+/// spawning and despawning an overlay for every flicker, which is worthwhile for entities that
+/// flicker constantly, such as with a [RepeatingFlicker].
+///
+/// Only the bottom layer of [FlickerOverlapAction::Stack] reuses the overlay, stacked flickers
+/// still get their own. The overlay is despawned when this component is removed, which cuts short
+/// a flicker running on it.
+#[derive(Component, Debug, Default, Reflect)]
+#[reflect(Component)]
+#[component(on_remove = despawn_persistent_overlay)]
+pub struct FlickerOverlay {
+    pub(crate) overlay: Option<Entity>,
+}
+
+fn despawn_persistent_overlay(mut world: DeferredWorld, HookContext { entity, .. }: HookContext) {
+    let Some(overlay) = world
+        .get::<FlickerOverlay>(entity)
+        .and_then(|flicker_overlay| flicker_overlay.overlay)
+    else {
+        return;
+    };
+    // A flicker running on the overlay is cut short the same way as with a FlickerStopEvent,
+    // as its timer goes away with the overlay
+    if let Some(flickered) = world.get::<Flickered>(overlay) {
+        let interrupted =
+            (!flickered.is_stopping()).then(|| FlickerInterrupted::from_flickered(flickered));
+        let still_flickering = world
+            .get::<Children>(entity)
+            .into_iter()
+            .flat_map(|children| children.iter())
+            .filter(|child| **child != overlay)
+            .any(|&child| {
+                world
+                    .get::<Flickered>(child)
+                    .is_some_and(|flickered| !flickered.is_finished())
+            });
+        if !still_flickering {
+            if let Some(mut queue) = world.get_mut::<FlickerQueue>(entity) {
+                // Anything waiting to play would otherwise never start
+                queue.0.clear();
+            }
+        }
+        let mut commands = world.commands();
+        if let Some(event) = interrupted {
+            commands.trigger(event.clone());
+            commands.write_message(event);
+        }
+        if !still_flickering {
+            if let Ok(mut entity_commands) = commands.get_entity(entity) {
+                entity_commands.try_remove::<FlickerMarker>();
+            }
+        }
+    }
+    if let Ok(mut entity_commands) = world.commands().get_entity(overlay) {
+        entity_commands.try_despawn();
     }
 }
 
+/// Placed on the overlay entity kept alive by [FlickerOverlay]
+#[derive(Component, Clone, Copy, Debug, Default, Reflect)]
+#[reflect(Component)]
+pub struct PersistentOverlay;
+
 /// An entity with this component will not react to flicker events
 #[derive(Component, Debug, Default, Reflect)]
 #[reflect(Component)]
@@ -32,17 +234,141 @@ pub struct NoFlicker;
 /// Marks an entity which is actively being flickered
 /// An extra marker is needed since no components are added to the entity
 /// being flickered.
-#[derive(Component, Debug, Default, Reflect)]
+#[derive(Component, Clone, Copy, Debug, Default, Reflect)]
 #[reflect(Component)]
+#[require(FlickerQueue)]
 pub struct FlickerMarker;
 
+/// Holds the flickers waiting for the active flicker on this entity to finish when using
+/// [FlickerOverlapAction::Queue][crate::config::FlickerOverlapAction::Queue].
+#[derive(Component, Clone, Debug, Default, Reflect)]
+#[reflect(Component)]
+pub struct FlickerQueue(pub(crate) VecDeque<FlickerStartEvent>);
+
+impl FlickerQueue {
+    /// The number of flickers waiting to play
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+/// Starts a flicker on the entity it is inserted on and then removes itself, so that an entity
+/// can be spawned flickering in a single bundle or from a scene, such as a freshly created item.
+///
+/// The flicker waits until the images and meshes it is drawn from have loaded, so the entity can
+/// be spawned with an image that is still loading. If `recursive` is set, it also waits for those
+/// of the entity's descendants. If one of them fails to load, a warning is logged and the
+/// component is removed without flickering.
+///
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_flicker::prelude::*;
+/// fn spawn_item(mut commands: Commands, asset_server: Res<AssetServer>) {
+///     commands.spawn((
+///         Sprite::from_image(asset_server.load("item.png")),
+///         Flicker {
+///             secs: 0.3,
+///             color: Color::WHITE,
+///             intensity: FlickerIntensity::FadeOut,
+///             ..default()
+///         },
+///     ));
+/// }
+/// ```
+#[derive(Component, Clone, Debug, Reflect)]
+#[reflect(Component, Default)]
+pub struct Flicker {
+    /// See [FlickerStartEvent] for more information
+    pub secs: f32,
+
+    /// See [FlickerStartEvent] for more information
+    pub color: Color,
+
+    /// See [FlickerStartEvent] for more information
+    pub gradient: Option<FlickerGradient>,
+
+    /// See [FlickerStartEvent] for more information
+    pub intensity: FlickerIntensity,
+
+    /// See [FlickerStartEvent] for more information
+    pub blend_mode: FlickerBlendMode,
+
+    /// See [FlickerStartEvent] for more information
+    pub recursive: bool,
+
+    /// See [FlickerStartEvent] for more information
+    pub overlap_action: Option<FlickerOverlapAction>,
+
+    /// See [FlickerStartEvent] for more information
+    pub mode: FlickerMode,
+
+    /// See [FlickerStartEvent] for more information
+    pub z_offset: Option<f32>,
+
+    /// See [FlickerStartEvent] for more information
+    pub style: FlickerStyle,
+}
+
+impl Default for Flicker {
+    fn default() -> Self {
+        // Shares its defaults with the flicker start event builder
+        let event = FlickerStartEventBuilder::default().build_for(Entity::PLACEHOLDER);
+        Self {
+            secs: event.secs,
+            color: event.color,
+            gradient: event.gradient,
+            intensity: event.intensity,
+            blend_mode: event.blend_mode,
+            recursive: event.recursive,
+            overlap_action: event.overlap_action,
+            mode: event.mode,
+            z_offset: event.z_offset,
+            style: event.style,
+        }
+    }
+}
+
+impl Flicker {
+    /// A flicker of the given length and color, with everything else left at its default
+    pub fn new(secs: f32, color: Color) -> Self {
+        Self {
+            secs,
+            color,
+            ..Default::default()
+        }
+    }
+
+    pub(crate) fn generate_start_event(&self, entity: Entity) -> FlickerStartEvent {
+        FlickerStartEvent {
+            entity,
+            secs: self.secs,
+            color: self.color,
+            gradient: self.gradient.clone(),
+            intensity: self.intensity.clone(),
+            blend_mode: self.blend_mode,
+            recursive: self.recursive,
+            overlap_action: self.overlap_action,
+            mode: self.mode,
+            z_offset: self.z_offset,
+            style: self.style.clone(),
+        }
+    }
+}
+
+/// [Flicker] under the name it is most often used for, spawning an entity with a flash
+pub type FlickerOnSpawn = Flicker;
+
 /// Sends [FlickerStartEvents][crate::events::FlickerStartEvent] on an interval.
 ///
 /// A pulse is a sequence of [RepeatingFlicker::pulse_count] flickers with a delay between
 /// each in the pulse of [RepeatingFlicker::time_between_flickers]. Each pulse has a delay of
 /// [RepeatingFlicker::time_between_pulses]. When [RepeatingFlicker::count] is set, after the
 /// set number of pulses occur, the [RepeatingFlicker] will be removed from the Entity.
-#[derive(Component, Reflect)]
+#[derive(Component, Clone, Reflect)]
 #[reflect(Component)]
 pub struct RepeatingFlicker {
     pub(crate) timer: Timer,
@@ -63,6 +389,30 @@ pub struct RepeatingFlicker {
     /// See [FlickerStartEvent][crate::events::FlickerStartEvent] for more information
     pub color: Color,
 
+    /// See [FlickerStartEvent][crate::events::FlickerStartEvent] for more information
+    pub gradient: Option<FlickerGradient>,
+
+    /// See [FlickerStartEvent][crate::events::FlickerStartEvent] for more information
+    pub intensity: FlickerIntensity,
+
+    /// See [FlickerStartEvent][crate::events::FlickerStartEvent] for more information
+    pub blend_mode: FlickerBlendMode,
+
+    /// See [FlickerStartEvent][crate::events::FlickerStartEvent] for more information
+    pub recursive: bool,
+
+    /// See [FlickerStartEvent][crate::events::FlickerStartEvent] for more information
+    pub overlap_action: Option<FlickerOverlapAction>,
+
+    /// See [FlickerStartEvent][crate::events::FlickerStartEvent] for more information
+    pub mode: FlickerMode,
+
+    /// See [FlickerStartEvent][crate::events::FlickerStartEvent] for more information
+    pub z_offset: Option<f32>,
+
+    /// See [FlickerStartEvent][crate::events::FlickerStartEvent] for more information
+    pub style: FlickerStyle,
+
     /// Number of flickers per pulse
     pub pulse_count: u32,
 
@@ -88,6 +438,14 @@ impl RepeatingFlicker {
             entity,
             secs: self.flicker_time_length,
             color: self.color,
+            gradient: self.gradient.clone(),
+            intensity: self.intensity.clone(),
+            blend_mode: self.blend_mode,
+            recursive: self.recursive,
+            overlap_action: self.overlap_action,
+            mode: self.mode,
+            z_offset: self.z_offset,
+            style: self.style.clone(),
         }
     }
 }
@@ -98,6 +456,14 @@ pub struct RepeatingFlickerBuilder {
     time_between_flickers: f32,
     time_between_pulses: f32,
     color: Color,
+    gradient: Option<FlickerGradient>,
+    intensity: FlickerIntensity,
+    blend_mode: FlickerBlendMode,
+    recursive: bool,
+    overlap_action: Option<FlickerOverlapAction>,
+    mode: FlickerMode,
+    z_offset: Option<f32>,
+    style: FlickerStyle,
     pulse_count: u32,
     count: Option<u32>,
 }
@@ -109,6 +475,14 @@ impl Default for RepeatingFlickerBuilder {
             time_between_flickers: 0.5,
             time_between_pulses: 0.5,
             color: Color::WHITE,
+            gradient: None,
+            intensity: FlickerIntensity::Constant,
+            blend_mode: FlickerBlendMode::Replace,
+            recursive: false,
+            overlap_action: None,
+            mode: FlickerMode::Overlay,
+            z_offset: None,
+            style: FlickerStyle::Fill,
             pulse_count: 1,
             count: None,
         }
@@ -135,6 +509,46 @@ impl RepeatingFlickerBuilder {
         self
     }
 
+    pub fn with_gradient(mut self, gradient: FlickerGradient) -> Self {
+        self.gradient = Some(gradient);
+        self
+    }
+
+    pub fn with_intensity(mut self, intensity: FlickerIntensity) -> Self {
+        self.intensity = intensity;
+        self
+    }
+
+    pub fn with_blend_mode(mut self, blend_mode: FlickerBlendMode) -> Self {
+        self.blend_mode = blend_mode;
+        self
+    }
+
+    pub fn with_recursive(mut self, recursive: bool) -> Self {
+        self.recursive = recursive;
+        self
+    }
+
+    pub fn with_overlap_action(mut self, overlap_action: FlickerOverlapAction) -> Self {
+        self.overlap_action = Some(overlap_action);
+        self
+    }
+
+    pub fn with_mode(mut self, mode: FlickerMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    pub fn with_z_offset(mut self, z_offset: f32) -> Self {
+        self.z_offset = Some(z_offset);
+        self
+    }
+
+    pub fn with_style(mut self, style: FlickerStyle) -> Self {
+        self.style = style;
+        self
+    }
+
     pub fn with_count(mut self, count: u32) -> Self {
         self.count = Some(count);
         self
@@ -160,6 +574,14 @@ impl RepeatingFlickerBuilder {
             flicker_time_length: self.flicker_time_length,
             time_between_flickers: self.time_between_flickers,
             color: self.color,
+            gradient: self.gradient,
+            intensity: self.intensity,
+            blend_mode: self.blend_mode,
+            recursive: self.recursive,
+            overlap_action: self.overlap_action,
+            mode: self.mode,
+            z_offset: self.z_offset,
+            style: self.style,
             count: self.count,
             time_between_pulses: self.time_between_pulses,
             pulse_count: self.pulse_count,
@@ -1,34 +1,96 @@
 use bevy_ecs::resource::Resource;
 
+use bevy_reflect::Reflect;
+
 /// Used to determine what to do when a flicker event is received for an
 /// entity that is already in a flickering state
-#[derive(PartialEq, Eq)]
+///
+/// The action used for a flicker is, in order of precedence, the one set on its
+/// [FlickerStartEvent][crate::events::FlickerStartEvent], the one set on the entity with
+/// [FlickerOverlapOverride][crate::components::FlickerOverlapOverride], or the global
+/// [FlickerPluginConfig::overlap_action].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Reflect)]
 pub enum FlickerOverlapAction {
     /// Flicker events targetted at entities that are already flickering will overwrite the
     /// existing flicker.
+    #[default]
     Overwrite,
 
     /// Flicker events targetted at entities that are already flickering will be ignored.
     Ignore,
+
+    /// Flicker events targetted at entities that are already flickering will be played once the
+    /// existing flicker, and any flickers queued before it, have finished.
+    Queue,
+
+    /// Flicker events targetted at entities that are already flickering will add their length to
+    /// the remaining time of the existing flicker, which otherwise stays unchanged.
+    Extend,
+
+    /// Whichever of the existing flicker and the new flicker would last longer is kept.
+    KeepLongest,
+
+    /// Whichever of the existing flicker and the new flicker is more opaque is kept. The
+    /// existing flicker is compared by its current alpha, the new flicker by the highest alpha it
+    /// will reach.
+    KeepStrongest,
+
+    /// Flicker events targetted at entities that are already flickering will layer a new overlay
+    /// on top of the existing ones, blending their colors together. Each overlay finishes on its
+    /// own.
+    Stack,
 }
 
-impl Default for FlickerOverlapAction {
-    fn default() -> Self {
-        FlickerOverlapAction::Overwrite
-    }
+/// Which clock flickers and [RepeatingFlickers][crate::components::RepeatingFlicker] advance
+/// with.
+///
+/// The clock used for an entity is the one set on it with
+/// [FlickerTimeSourceOverride][crate::components::FlickerTimeSourceOverride], or the global
+/// [FlickerPluginConfig::time_source]. Either can be sped up or slowed down for a single entity
+/// with [FlickerTimeScale][crate::components::FlickerTimeScale].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Reflect)]
+pub enum FlickerTimeSource {
+    /// The game's clock, `Time<Virtual>`. Flickers freeze while it is paused and slow down
+    /// along with it, such as for bullet-time.
+    ///
+    /// When the flicker systems run in `FixedUpdate` this is the fixed clock, which still
+    /// follows virtual time but advances in whole timesteps, see
+    /// [FlickerPlugin::with_schedule][crate::FlickerPlugin::with_schedule].
+    #[default]
+    Virtual,
+
+    /// The wall clock, `Time<Real>`. Flickers keep running while virtual time is paused or
+    /// scaled, such as for UI shown over a paused game.
+    ///
+    /// Not suited to `FixedUpdate`, where flickers would advance by the length of a whole frame
+    /// on every timestep.
+    Real,
 }
 
-#[derive(Resource, Default)]
+#[derive(Resource)]
 pub struct FlickerPluginConfig {
-    /// See [FlickerOverlapAction]
+    /// The overlap action used when neither the flicker event nor the entity specify one, see
+    /// [FlickerOverlapAction]
     pub overlap_action: FlickerOverlapAction,
+
+    /// How far in front of the flickered entity 2D overlays are placed, used when the flicker
+    /// event does not set one. Defaults to 1.0.
+    ///
+    /// Any sprite placed between the entity and its overlay is drawn under the overlay, so this
+    /// can be lowered to keep sprites that sit just in front of the entity, such as a held item,
+    /// drawn over the flicker.
+    pub z_offset: f32,
+
+    /// The clock used when the entity does not specify one, see [FlickerTimeSource]
+    pub time_source: FlickerTimeSource,
 }
 
-impl FlickerPluginConfig {
-    pub(crate) fn ignore_overlap(&self) -> bool {
-        match self.overlap_action {
-            FlickerOverlapAction::Ignore => true,
-            _ => false,
+impl Default for FlickerPluginConfig {
+    fn default() -> Self {
+        Self {
+            overlap_action: FlickerOverlapAction::default(),
+            z_offset: 1.0,
+            time_source: FlickerTimeSource::default(),
         }
     }
 }
@@ -1,8 +1,22 @@
-use bevy_ecs::{entity::Entity, message::Message};
+use bevy_ecs::{
+    entity::Entity,
+    event::EntityEvent,
+    message::{Message, MessageWriter},
+    reflect::ReflectResource,
+    resource::Resource,
+    system::{Commands, SystemParam},
+};
 
-use bevy_color::Color;
+use bevy_color::{Alpha, Color};
 
-#[derive(Debug, Message)]
+use bevy_reflect::Reflect;
+
+use crate::{
+    blend::FlickerBlendMode, components::Flickered, config::FlickerOverlapAction,
+    gradient::FlickerGradient, intensity::FlickerIntensity, mode::FlickerMode, style::FlickerStyle,
+};
+
+#[derive(Debug, Clone, Message, Reflect)]
 pub struct FlickerStartEvent {
     /// Entity to apply the flicker to
     pub entity: Entity,
@@ -12,18 +26,69 @@ pub struct FlickerStartEvent {
 
     /// The flicker color that will be blending with the original color
     pub color: Color,
+
+    /// When set, the flicker moves through these colors over its lifetime instead of using
+    /// [FlickerStartEvent::color]
+    pub gradient: Option<FlickerGradient>,
+
+    /// How the strength of the flicker changes over its lifetime, see [FlickerIntensity]
+    pub intensity: FlickerIntensity,
+
+    /// How the flicker color is combined with the underlying sprite, see [FlickerBlendMode]
+    pub blend_mode: FlickerBlendMode,
+
+    /// When true, the flicker is also applied to every descendant of the entity, skipping any
+    /// subtree whose root has [NoFlicker][crate::components::NoFlicker]. The whole hierarchy
+    /// shares a single timer and finishes at the same time.
+    pub recursive: bool,
+
+    /// What to do if the entity is already flickering, overrides both the entity's
+    /// [FlickerOverlapOverride][crate::components::FlickerOverlapOverride] and the global
+    /// [FlickerPluginConfig][crate::config::FlickerPluginConfig] when set.
+    pub overlap_action: Option<FlickerOverlapAction>,
+
+    /// Whether the flicker is drawn on an overlay or changes the color of the entity itself, see
+    /// [FlickerMode]
+    pub mode: FlickerMode,
+
+    /// How far in front of the entity a 2D overlay is placed, overrides
+    /// [FlickerPluginConfig::z_offset][crate::config::FlickerPluginConfig::z_offset] when set
+    pub z_offset: Option<f32>,
+
+    /// Which part of the sprite the flicker is drawn over, see [FlickerStyle]
+    pub style: FlickerStyle,
 }
 
 impl FlickerStartEvent {
     pub fn builder(entity: Entity) -> FlickerStartEventBuilder {
         FlickerStartEventBuilder::new(entity)
     }
+
+    /// The highest alpha this flicker's color will reach
+    pub fn strength(&self) -> f32 {
+        match self.gradient.as_ref() {
+            Some(gradient) => gradient
+                .stops()
+                .iter()
+                .map(|stop| stop.color.alpha())
+                .fold(0.0, f32::max),
+            None => self.color.alpha(),
+        }
+    }
 }
 
 pub struct FlickerStartEventBuilder {
     entity: Option<Entity>, // Entity cannot have a default, so Option is used.
     secs: f32,
     color: Color,
+    gradient: Option<FlickerGradient>,
+    intensity: FlickerIntensity,
+    blend_mode: FlickerBlendMode,
+    recursive: bool,
+    overlap_action: Option<FlickerOverlapAction>,
+    mode: FlickerMode,
+    z_offset: Option<f32>,
+    style: FlickerStyle,
 }
 
 impl Default for FlickerStartEventBuilder {
@@ -32,6 +97,14 @@ impl Default for FlickerStartEventBuilder {
             entity: None,
             secs: 0.1,
             color: Color::WHITE,
+            gradient: None,
+            intensity: FlickerIntensity::Constant,
+            blend_mode: FlickerBlendMode::Replace,
+            recursive: false,
+            overlap_action: None,
+            mode: FlickerMode::Overlay,
+            z_offset: None,
+            style: FlickerStyle::Fill,
         }
     }
 }
@@ -54,11 +127,197 @@ impl FlickerStartEventBuilder {
         self
     }
 
+    pub fn with_gradient(mut self, gradient: FlickerGradient) -> Self {
+        self.gradient = Some(gradient);
+        self
+    }
+
+    pub fn with_intensity(mut self, intensity: FlickerIntensity) -> Self {
+        self.intensity = intensity;
+        self
+    }
+
+    pub fn with_blend_mode(mut self, blend_mode: FlickerBlendMode) -> Self {
+        self.blend_mode = blend_mode;
+        self
+    }
+
+    pub fn with_recursive(mut self, recursive: bool) -> Self {
+        self.recursive = recursive;
+        self
+    }
+
+    pub fn with_overlap_action(mut self, overlap_action: FlickerOverlapAction) -> Self {
+        self.overlap_action = Some(overlap_action);
+        self
+    }
+
+    pub fn with_mode(mut self, mode: FlickerMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    pub fn with_z_offset(mut self, z_offset: f32) -> Self {
+        self.z_offset = Some(z_offset);
+        self
+    }
+
+    pub fn with_style(mut self, style: FlickerStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Builds the event, panics if the builder was made without an entity through [Default]
+    /// rather than [FlickerStartEvent::builder]
     pub fn build(self) -> FlickerStartEvent {
+        let entity = self
+            .entity
+            .expect("FlickerStartEventBuilder needs an entity, see FlickerStartEvent::builder");
+        self.build_for(entity)
+    }
+
+    /// Builds the event for the given entity, used where the builder was made without one
+    pub(crate) fn build_for(self, entity: Entity) -> FlickerStartEvent {
         FlickerStartEvent {
-            entity: self.entity.unwrap(), // Guaranteed to not be None
+            entity,
             secs: self.secs,
             color: self.color,
+            gradient: self.gradient,
+            intensity: self.intensity,
+            blend_mode: self.blend_mode,
+            recursive: self.recursive,
+            overlap_action: self.overlap_action,
+            mode: self.mode,
+            z_offset: self.z_offset,
+            style: self.style,
         }
     }
 }
+
+/// Stops the active flicker on an entity before it finishes.
+///
+/// The overlay is removed, along with the [FlickerMarker][crate::components::FlickerMarker] on
+/// the entity, and a [FlickerInterrupted] is sent. If [FlickerStopEvent::fade_secs] is set, the
+/// overlay first fades out over that many seconds. This does not remove a
+/// [RepeatingFlicker][crate::components::RepeatingFlicker], which will keep starting new flickers.
+///
+/// This can also be sent through
+/// [FlickerCommandsExt][crate::commands::FlickerCommandsExt::stop_flicker].
+#[derive(Debug, Clone, Message)]
+pub struct FlickerStopEvent {
+    /// Entity to stop flickering
+    pub entity: Entity,
+
+    /// How long in seconds the overlay takes to fade out, stops instantly when None
+    pub fade_secs: Option<f32>,
+}
+
+impl FlickerStopEvent {
+    /// Stops the flicker instantly
+    pub fn new(entity: Entity) -> Self {
+        Self {
+            entity,
+            fade_secs: None,
+        }
+    }
+
+    /// Fades the flicker out over the given number of seconds
+    pub fn with_fade(entity: Entity, fade_secs: f32) -> Self {
+        Self {
+            entity,
+            fade_secs: Some(fade_secs),
+        }
+    }
+}
+
+/// Sent when a flicker has begun on an entity.
+///
+/// This is both a [Message] and an [EntityEvent], so it can be read with a
+/// [MessageReader][bevy_ecs::message::MessageReader] or observed on the flickered entity.
+#[derive(Debug, Clone, Message, EntityEvent)]
+pub struct FlickerStarted {
+    /// The flickered entity
+    pub entity: Entity,
+
+    /// The parameters of the flicker
+    pub flicker: FlickerStartEvent,
+}
+
+/// Sent when a flicker has run for its full length and its overlay has been removed.
+///
+/// See [FlickerStarted] for how to receive it.
+#[derive(Debug, Clone, Message, EntityEvent)]
+pub struct FlickerEnded {
+    /// The flickered entity
+    pub entity: Entity,
+
+    /// The parameters of the flicker
+    pub flicker: FlickerStartEvent,
+}
+
+/// Sent when a flicker is cut short before it finished, such as when it is overwritten by a new
+/// flicker or stopped with a [FlickerStopEvent].
+///
+/// See [FlickerStarted] for how to receive it.
+#[derive(Debug, Clone, Message, EntityEvent)]
+pub struct FlickerInterrupted {
+    /// The flickered entity
+    pub entity: Entity,
+
+    /// The parameters of the flicker that was interrupted
+    pub flicker: FlickerStartEvent,
+
+    /// How far along the flicker was when it was interrupted, between 0.0 and 1.0
+    pub progress: f32,
+}
+
+impl FlickerInterrupted {
+    pub(crate) fn from_flickered(flickered: &Flickered) -> Self {
+        Self {
+            entity: flickered.event.entity,
+            flicker: flickered.event.clone(),
+            progress: flickered.progress(),
+        }
+    }
+}
+
+/// Flicker start events held over to the next run of the flicker systems, as the entity they are
+/// for already had a flicker started in the same run. Part of the flicker state to snapshot when
+/// using rollback, see [FlickerPlugin::with_schedule][crate::FlickerPlugin::with_schedule].
+#[derive(Resource, Clone, Debug, Default, Reflect)]
+#[reflect(Resource)]
+pub struct FlickerPendingStarts(pub(crate) Vec<FlickerStartEvent>);
+
+/// Sends the lifecycle messages and triggers their matching entity events
+#[derive(SystemParam)]
+pub(crate) struct FlickerLifecycle<'w> {
+    started: MessageWriter<'w, FlickerStarted>,
+    ended: MessageWriter<'w, FlickerEnded>,
+    interrupted: MessageWriter<'w, FlickerInterrupted>,
+}
+
+impl FlickerLifecycle<'_> {
+    pub(crate) fn started(&mut self, commands: &mut Commands, flicker: &FlickerStartEvent) {
+        let event = FlickerStarted {
+            entity: flicker.entity,
+            flicker: flicker.clone(),
+        };
+        commands.trigger(event.clone());
+        self.started.write(event);
+    }
+
+    pub(crate) fn ended(&mut self, commands: &mut Commands, flickered: &Flickered) {
+        let event = FlickerEnded {
+            entity: flickered.event.entity,
+            flicker: flickered.event.clone(),
+        };
+        commands.trigger(event.clone());
+        self.ended.write(event);
+    }
+
+    pub(crate) fn interrupted(&mut self, commands: &mut Commands, flickered: &Flickered) {
+        let event = FlickerInterrupted::from_flickered(flickered);
+        commands.trigger(event.clone());
+        self.interrupted.write(event);
+    }
+}
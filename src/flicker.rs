@@ -7,8 +7,20 @@ use bevy_render::render_resource::AsBindGroup;
 use bevy_shader::ShaderRef;
 
 use bevy_color::LinearRgba;
+#[cfg(feature = "3d")]
+use bevy_pbr::{ExtendedMaterial, MaterialExtension, StandardMaterial};
+#[cfg(feature = "3d")]
+use bevy_render::alpha::AlphaMode;
 
+use crate::blend::FlickerBlendMode;
+use crate::shader::{FlickerInputs, FlickerShader};
+use crate::style::FlickerStyle;
 
+#[cfg(feature = "ui")]
+use bevy_ui_render::prelude::UiMaterial;
+
+
+/// The material that 2D overlays are drawn with by default, see [FlickerShader] to use another
 #[derive(AsBindGroup, Clone, Reflect, Asset)]
 pub struct FlickerMaterial {
     #[texture(0)]
@@ -36,6 +48,32 @@ pub struct FlickerMaterial {
     /// The color to flicker, will be mixed with each pixel
     #[uniform(2)]
     pub color: LinearRgba,
+
+    /// How the color is combined with each pixel, see [FlickerBlendMode]
+    #[uniform(2)]
+    pub blend_mode: u32,
+
+    /// Which part of the sprite is drawn over, see [FlickerStyle]
+    #[uniform(2)]
+    pub style: u32,
+
+    /// The width of the outline in pixels of the source image, see [FlickerStyle::Outline]
+    #[uniform(2)]
+    pub outline_thickness: f32,
+
+    /// How far along the flicker is, between 0.0 and 1.0
+    #[uniform(2)]
+    pub progress: f32,
+
+    /// The size in pixels of the source image that one repeat of the noise covers, see
+    /// [FlickerStyle::Dissolve]
+    #[uniform(2)]
+    pub noise_scale: f32,
+
+    /// The noise used by [FlickerStyle::Dissolve], built-in noise is used when None
+    #[texture(3)]
+    #[sampler(4)]
+    pub noise_image: Option<Handle<Image>>,
 }
 
 impl Material2d for FlickerMaterial {
@@ -55,6 +93,142 @@ impl Default for FlickerMaterial {
             size: Vec2::splat(1.0),
             ratio: Vec2::splat(1.0),
             color: LinearRgba::new(1.0, 1.0, 1.0, 1.0),
+            blend_mode: FlickerBlendMode::Replace.shader_index(),
+            style: FlickerStyle::Fill.shader_index(),
+            outline_thickness: 0.0,
+            progress: 0.0,
+            noise_scale: 1.0,
+            noise_image: None,
+        }
+    }
+}
+
+impl FlickerShader for FlickerMaterial {
+    fn from_inputs(inputs: &FlickerInputs) -> Self {
+        let mut material = Self::default();
+        material.set_inputs(inputs);
+        material
+    }
+
+    fn set_inputs(&mut self, inputs: &FlickerInputs) {
+        let (noise_image, noise_scale) = inputs.style.noise();
+        self.source_image = inputs.source_image.clone();
+        self.offset = inputs.offset;
+        self.size = inputs.size;
+        self.color = inputs.color;
+        self.blend_mode = inputs.blend_mode.shader_index();
+        self.style = inputs.style.shader_index();
+        self.outline_thickness = inputs.style.outline_thickness();
+        self.progress = inputs.progress;
+        self.noise_scale = noise_scale;
+        self.noise_image = noise_image;
+    }
+
+    fn uses_progress(inputs: &FlickerInputs) -> bool {
+        inputs.style.is_animated()
+    }
+}
+
+/// The material used to flicker 3D meshes, keeps the lighting of the flickered entity's
+/// [StandardMaterial] and applies the flicker color on top of it.
+#[cfg(feature = "3d")]
+pub type FlickerMaterial3d = ExtendedMaterial<StandardMaterial, FlickerExtension>;
+
+#[cfg(feature = "3d")]
+#[derive(AsBindGroup, Clone, Reflect, Asset)]
+pub struct FlickerExtension {
+    /// The color to flicker, will be mixed with each lit pixel
+    #[uniform(100)]
+    pub color: LinearRgba,
+
+    /// How the color is combined with each lit pixel, see [FlickerBlendMode]
+    #[uniform(100)]
+    pub blend_mode: u32,
+}
+
+#[cfg(feature = "3d")]
+impl MaterialExtension for FlickerExtension {
+    fn fragment_shader() -> ShaderRef {
+        "embedded://flicker_material_3d.wgsl".into()
+    }
+
+    fn alpha_mode() -> Option<AlphaMode> {
+        Some(AlphaMode::Blend)
+    }
+
+    // The overlay is drawn over its parent, so it should not take part in the depth prepass
+    // or cast a second shadow.
+    fn enable_prepass() -> bool {
+        false
+    }
+
+    fn enable_shadows() -> bool {
+        false
+    }
+}
+
+#[cfg(feature = "3d")]
+impl Default for FlickerExtension {
+    fn default() -> Self {
+        Self {
+            color: LinearRgba::new(1.0, 1.0, 1.0, 1.0),
+            blend_mode: FlickerBlendMode::Replace.shader_index(),
+        }
+    }
+}
+
+/// The material used to flicker UI nodes. It redraws the look of the node, its image or its
+/// background and border colors, and applies the flicker color on top of it.
+#[cfg(feature = "ui")]
+#[derive(AsBindGroup, Clone, Reflect, Asset)]
+pub struct FlickerUiMaterial {
+    #[texture(0)]
+    #[sampler(1)]
+    pub source_image: Option<Handle<Image>>,
+
+    /// Where the part of the image the node shows starts, as a percentage of the image
+    #[uniform(2)]
+    pub offset: Vec2,
+
+    /// How large the part of the image the node shows is, as a percentage of the image
+    #[uniform(2)]
+    pub size: Vec2,
+
+    /// The color of the node inside of its border, the image is multiplied by it
+    #[uniform(2)]
+    pub base_color: LinearRgba,
+
+    /// The colors of the node's border, in the order top, right, bottom, left
+    #[uniform(2)]
+    pub border_colors: [LinearRgba; 4],
+
+    /// The color to flicker, will be mixed with each pixel
+    #[uniform(2)]
+    pub color: LinearRgba,
+
+    /// How the color is combined with each pixel, see [FlickerBlendMode]
+    #[uniform(2)]
+    pub blend_mode: u32,
+}
+
+#[cfg(feature = "ui")]
+impl UiMaterial for FlickerUiMaterial {
+    fn fragment_shader() -> ShaderRef {
+        "embedded://flicker_ui_material.wgsl".into()
+    }
+}
+
+#[cfg(feature = "ui")]
+impl Default for FlickerUiMaterial {
+    fn default() -> Self {
+        Self {
+            source_image: Default::default(),
+            offset: Default::default(),
+            size: Vec2::splat(1.0),
+            base_color: LinearRgba::WHITE,
+            border_colors: [LinearRgba::NONE; 4],
+            color: LinearRgba::new(1.0, 1.0, 1.0, 1.0),
+            blend_mode: FlickerBlendMode::Replace.shader_index(),
         }
     }
 }
@@ -0,0 +1,33 @@
+#define_import_path bevy_flicker::blend
+
+fn luminance(color: vec3<f32>) -> f32 {
+    return dot(color, vec3<f32>(0.2126, 0.7152, 0.0722));
+}
+
+// Combines the flicker color with the underlying color according to the blend mode, see
+// FlickerBlendMode for the values of each mode.
+fn blend(base: vec3<f32>, flicker: vec3<f32>, blend_mode: u32) -> vec3<f32> {
+    switch blend_mode {
+        case 1u: { // Multiply
+            return base * flicker;
+        }
+        case 2u: { // Add
+            return min(base + flicker, vec3<f32>(1.0));
+        }
+        case 3u: { // Screen
+            return 1.0 - (1.0 - base) * (1.0 - flicker);
+        }
+        case 4u: { // Overlay
+            let low = 2.0 * base * flicker;
+            let high = 1.0 - 2.0 * (1.0 - base) * (1.0 - flicker);
+            return select(high, low, base < vec3<f32>(0.5));
+        }
+        case 5u: { // Tint
+            // Scale the flicker color so it has the same luminance as the texel
+            return flicker * (luminance(base) / max(luminance(flicker), 0.0001));
+        }
+        default: { // Replace
+            return flicker;
+        }
+    }
+}
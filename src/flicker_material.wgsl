@@ -1,6 +1,7 @@
 #import bevy_sprite::mesh2d_view_bindings
 #import bevy_pbr::utils
 #import bevy_sprite::mesh2d_vertex_output::VertexOutput
+#import bevy_flicker::blend::blend
 @group(2) @binding(0)
 var texture: texture_2d<f32>;
 
@@ -14,13 +15,88 @@ struct FlickerMaterial {
     size: vec2<f32>,
     ratio: vec2<f32>,
     color: vec4<f32>,
+    blend_mode: u32,
+    style: u32,
+    outline_thickness: f32,
+    progress: f32,
+    noise_scale: f32,
 }
 
 @group(2) @binding(2)
 var<uniform> flicker_material: FlickerMaterial;
 
+@group(2) @binding(3)
+var noise_texture: texture_2d<f32>;
+
+@group(2) @binding(4)
+var noise_sampler: sampler;
+
 //let ZERO: vec2<f32> = vec2<f32>(0.0, 0.0);
 
+const PI: f32 = 3.14159265;
+
+// Outlines wider than this many pixels are cut down to it, to bound the number of samples
+const MAX_OUTLINE_STEPS: i32 = 32;
+
+// Whether the image is opaque at the given point, anything outside of the part of the image the
+// sprite shows counts as transparent.
+fn is_opaque(uv: vec2<f32>, rect_min: vec2<f32>, rect_max: vec2<f32>) -> bool {
+    if any(uv < rect_min) || any(uv > rect_max) {
+        return false;
+    }
+    return textureSampleLevel(texture, our_sampler, uv, 0.0).a > 0.5;
+}
+
+// Whether there is a transparent pixel within the outline thickness of the given point
+fn is_near_edge(uv: vec2<f32>) -> bool {
+    let texel_size = 1.0 / vec2<f32>(textureDimensions(texture));
+    let rect_start = flicker_material.offset;
+    let rect_end = flicker_material.offset + flicker_material.size;
+    let rect_min = min(rect_start, rect_end);
+    let rect_max = max(rect_start, rect_end);
+
+    let thickness = flicker_material.outline_thickness;
+    let steps = min(i32(ceil(thickness)), MAX_OUTLINE_STEPS);
+    for (var step = 1; step <= steps; step++) {
+        let distance = min(f32(step), thickness);
+        for (var i = 0; i < 8; i++) {
+            let angle = f32(i) * PI / 4.0;
+            let direction = vec2<f32>(cos(angle), sin(angle));
+            if !is_opaque(uv + direction * distance * texel_size, rect_min, rect_max) {
+                return true;
+            }
+        }
+    }
+    return false;
+}
+
+// A pseudo-random value between 0 and 1 for the given cell
+fn hash(cell: vec2<f32>) -> f32 {
+    let p = fract(cell * vec2<f32>(123.34, 456.21));
+    let q = p + dot(p, p + 45.32);
+    return fract(q.x * q.y);
+}
+
+// Smooth value noise between 0 and 1, with one random value per cell
+fn value_noise(point: vec2<f32>) -> f32 {
+    let cell = floor(point);
+    let local = fract(point);
+    let t = local * local * (3.0 - 2.0 * local);
+    let bottom = mix(hash(cell), hash(cell + vec2<f32>(1.0, 0.0)), t.x);
+    let top = mix(hash(cell + vec2<f32>(0.0, 1.0)), hash(cell + vec2<f32>(1.0, 1.0)), t.x);
+    return mix(bottom, top, t.y);
+}
+
+// The dissolve noise at the given point of the source image, the noise image repeats every
+// noise_scale pixels when one is used.
+fn dissolve_noise(uv: vec2<f32>) -> f32 {
+    let point = uv * vec2<f32>(textureDimensions(texture)) / flicker_material.noise_scale;
+    if flicker_material.style == 3u {
+        return textureSampleLevel(noise_texture, noise_sampler, fract(point), 0.0).r;
+    }
+    return value_noise(point);
+}
+
 @fragment
 fn fragment(in: VertexOutput) -> @location(0) vec4<f32> {
     // Get screen position with coordinates from 0 to 1
@@ -29,6 +105,18 @@ fn fragment(in: VertexOutput) -> @location(0) vec4<f32> {
     let uv = ((in.uv * new_range) + flicker_material.offset);
     let color = textureSample(texture, our_sampler, uv);
 
-    // Return the flicker color except keep the alpha consistent with the underlying texture
-    return vec4<f32>(flicker_material.color.r, flicker_material.color.g, flicker_material.color.b, flicker_material.color.a * color.a);
+    // Outlines only cover the opaque pixels along the edge of the sprite
+    var coverage = 1.0;
+    if flicker_material.style == 1u {
+        coverage = select(0.0, 1.0, color.a > 0.5 && is_near_edge(uv));
+    }
+
+    // Dissolves eat away at the parts of the flicker where the noise is below the progress
+    if flicker_material.style == 2u || flicker_material.style == 3u {
+        coverage = select(0.0, 1.0, dissolve_noise(uv) >= flicker_material.progress);
+    }
+
+    // Return the blended color except keep the alpha consistent with the underlying texture
+    let blended = blend(color.rgb, flicker_material.color.rgb, flicker_material.blend_mode);
+    return vec4<f32>(blended, flicker_material.color.a * color.a * coverage);
 }
@@ -0,0 +1,33 @@
+#import bevy_pbr::{
+    pbr_fragment::pbr_input_from_standard_material,
+    pbr_functions::{alpha_discard, apply_pbr_lighting, main_pass_post_lighting_processing},
+    forward_io::{VertexOutput, FragmentOutput},
+}
+#import bevy_flicker::blend::blend
+
+struct FlickerExtension {
+    color: vec4<f32>,
+    blend_mode: u32,
+}
+
+@group(#{MATERIAL_BIND_GROUP}) @binding(100)
+var<uniform> flicker_extension: FlickerExtension;
+
+@fragment
+fn fragment(
+    in: VertexOutput,
+    @builtin(front_facing) is_front: bool,
+) -> FragmentOutput {
+    // Light the mesh exactly as its StandardMaterial would
+    var pbr_input = pbr_input_from_standard_material(in, is_front);
+    pbr_input.material.base_color = alpha_discard(pbr_input.material, pbr_input.material.base_color);
+    let lit = apply_pbr_lighting(pbr_input);
+
+    // Use the blended color except keep the alpha consistent with the underlying material
+    let blended = blend(lit.rgb, flicker_extension.color.rgb, flicker_extension.blend_mode);
+    let color = vec4<f32>(blended, flicker_extension.color.a * pbr_input.material.base_color.a);
+
+    var out: FragmentOutput;
+    out.color = main_pass_post_lighting_processing(pbr_input, color);
+    return out;
+}
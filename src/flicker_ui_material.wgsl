@@ -0,0 +1,65 @@
+#import bevy_ui::ui_vertex_output::UiVertexOutput
+#import bevy_flicker::blend::blend
+
+@group(1) @binding(0)
+var texture: texture_2d<f32>;
+
+@group(1) @binding(1)
+var our_sampler: sampler;
+
+struct FlickerUiMaterial {
+    offset: vec2<f32>,
+    size: vec2<f32>,
+    base_color: vec4<f32>,
+    // Top, right, bottom, left
+    border_colors: array<vec4<f32>, 4>,
+    color: vec4<f32>,
+    blend_mode: u32,
+}
+
+@group(1) @binding(2)
+var<uniform> flicker_material: FlickerUiMaterial;
+
+// The distance from the point to the edge of the rounded box, negative inside of it. The same as
+// the one bevy_ui draws its nodes with.
+fn sd_rounded_box(point: vec2<f32>, size: vec2<f32>, corner_radii: vec4<f32>) -> f32 {
+    let rs = select(corner_radii.xy, corner_radii.wz, 0.0 < point.y);
+    let radius = select(rs.x, rs.y, 0.0 < point.x);
+    let corner_to_point = abs(point) - 0.5 * size;
+    let q = corner_to_point + radius;
+    let l = length(max(q, vec2(0.0)));
+    let m = min(max(q.x, q.y), 0.0);
+    return l + m - radius;
+}
+
+@fragment
+fn fragment(in: UiVertexOutput) -> @location(0) vec4<f32> {
+    let uv = ((in.uv * flicker_material.size) + flicker_material.offset);
+    let texel = textureSample(texture, our_sampler, uv);
+
+    // Pick the color of the node at this point, either its inside or one of its borders
+    let point = (in.uv - 0.5) * in.size;
+    let from_min = in.uv * in.size;
+    let from_max = in.size - from_min;
+    // Left, right, top, bottom
+    let borders = in.border_widths * in.size.xxyy;
+    var base = flicker_material.base_color;
+    if from_min.y < borders.z {
+        base = flicker_material.border_colors[0];
+    } else if from_max.x < borders.y {
+        base = flicker_material.border_colors[1];
+    } else if from_max.y < borders.w {
+        base = flicker_material.border_colors[2];
+    } else if from_min.x < borders.x {
+        base = flicker_material.border_colors[3];
+    }
+    let color = texel * base;
+
+    // Only cover the node itself, following its rounded corners
+    let distance = sd_rounded_box(point, in.size, in.border_radius);
+    let coverage = saturate(0.5 - distance);
+
+    // Return the blended color except keep the alpha consistent with the underlying node
+    let blended = blend(color.rgb, flicker_material.color.rgb, flicker_material.blend_mode);
+    return vec4<f32>(blended, flicker_material.color.a * color.a * coverage);
+}
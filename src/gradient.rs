@@ -0,0 +1,114 @@
+use bevy_color::{Color, Hsva, LinearRgba, Mix, Oklaba, Srgba};
+use bevy_reflect::Reflect;
+
+/// The color space used to interpolate between the stops of a [FlickerGradient].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Reflect)]
+pub enum FlickerColorSpace {
+    /// Interpolates each channel linearly, this is the color space the overlay is rendered in.
+    #[default]
+    LinearRgba,
+
+    /// Interpolates in gamma-corrected sRGB.
+    Srgba,
+
+    /// Interpolates in Oklab, which gives perceptually even transitions.
+    Oklaba,
+
+    /// Interpolates hue, saturation and value, taking the shortest path around the hue wheel.
+    Hsva,
+}
+
+impl FlickerColorSpace {
+    /// Mixes the two colors in this color space
+    pub fn mix(&self, a: Color, b: Color, factor: f32) -> LinearRgba {
+        match self {
+            Self::LinearRgba => LinearRgba::from(a).mix(&LinearRgba::from(b), factor),
+            Self::Srgba => Srgba::from(a).mix(&Srgba::from(b), factor).into(),
+            Self::Oklaba => Oklaba::from(a).mix(&Oklaba::from(b), factor).into(),
+            Self::Hsva => Hsva::from(a).mix(&Hsva::from(b), factor).into(),
+        }
+    }
+}
+
+/// A single stop in a [FlickerGradient].
+#[derive(Clone, Copy, Debug, Reflect)]
+pub struct FlickerColorStop {
+    /// Where along the flicker this color is reached, between 0.0 and 1.0
+    pub position: f32,
+
+    /// The color at this stop
+    pub color: Color,
+}
+
+/// A sequence of colors the flicker moves through over its lifetime.
+///
+/// The gradient is sampled with the progress of the flicker, where 0.0 is the moment the flicker
+/// starts and 1.0 is the moment it ends.
+#[derive(Clone, Debug, Default, Reflect)]
+pub struct FlickerGradient {
+    stops: Vec<FlickerColorStop>,
+
+    /// See [FlickerColorSpace]
+    pub color_space: FlickerColorSpace,
+}
+
+impl FlickerGradient {
+    /// Creates a gradient with the given colors spread evenly over the flicker.
+    pub fn new(colors: impl IntoIterator<Item = Color>) -> Self {
+        let colors = colors.into_iter().collect::<Vec<_>>();
+        let last = colors.len().saturating_sub(1).max(1) as f32;
+        Self {
+            stops: colors
+                .into_iter()
+                .enumerate()
+                .map(|(i, color)| FlickerColorStop {
+                    position: i as f32 / last,
+                    color,
+                })
+                .collect(),
+            color_space: Default::default(),
+        }
+    }
+
+    /// Adds a color stop at the given position, between 0.0 and 1.0
+    pub fn with_stop(mut self, position: f32, color: Color) -> Self {
+        let stop = FlickerColorStop { position, color };
+        let index = self.stops.partition_point(|s| s.position <= position);
+        self.stops.insert(index, stop);
+        self
+    }
+
+    pub fn with_color_space(mut self, color_space: FlickerColorSpace) -> Self {
+        self.color_space = color_space;
+        self
+    }
+
+    /// The color stops, ordered by position
+    pub fn stops(&self) -> &[FlickerColorStop] {
+        &self.stops
+    }
+
+    /// Samples the gradient at the given progress, between 0.0 and 1.0.
+    ///
+    /// Returns `None` if the gradient has no stops.
+    pub fn sample(&self, progress: f32) -> Option<LinearRgba> {
+        let t = progress.clamp(0.0, 1.0);
+        let next = self.stops.partition_point(|s| s.position <= t);
+        match (
+            next.checked_sub(1).map(|i| self.stops[i]),
+            self.stops.get(next).copied(),
+        ) {
+            (Some(prev), Some(next)) => {
+                let span = next.position - prev.position;
+                let factor = if span > 0.0 {
+                    (t - prev.position) / span
+                } else {
+                    1.0
+                };
+                Some(self.color_space.mix(prev.color, next.color, factor))
+            }
+            (Some(only), None) | (None, Some(only)) => Some(only.color.into()),
+            (None, None) => None,
+        }
+    }
+}
@@ -0,0 +1,100 @@
+use std::{fmt, sync::Arc};
+
+use bevy_math::curve::{Curve, EaseFunction};
+use bevy_reflect::Reflect;
+
+/// Describes how the strength of a flicker changes over its lifetime.
+///
+/// The intensity is sampled with the progress of the flicker, where 0.0 is the moment the flicker
+/// starts and 1.0 is the moment it ends. The sampled value is multiplied with the alpha of the
+/// flicker color, so 1.0 is full strength and 0.0 is fully transparent.
+#[derive(Clone, Debug, Default, Reflect)]
+pub enum FlickerIntensity {
+    /// Full strength for the entire length of the flicker.
+    #[default]
+    Constant,
+
+    /// Linearly fades from full strength down to nothing.
+    FadeOut,
+
+    /// Linearly fades from nothing up to full strength.
+    FadeIn,
+
+    /// Smoothly eases up to full strength halfway through the flicker, then smoothly eases back
+    /// down to nothing.
+    EaseInOut,
+
+    /// Follows one of bevy's easing functions over the length of the flicker.
+    Ease(EaseFunction),
+
+    /// An attack, decay, sustain, release envelope.
+    ///
+    /// `attack`, `decay` and `release` are fractions of the flicker length between 0.0 and 1.0,
+    /// `sustain` is the intensity held between the decay and the release.
+    Adsr {
+        attack: f32,
+        decay: f32,
+        sustain: f32,
+        release: f32,
+    },
+
+    /// A user-supplied curve, see [FlickerCurve].
+    Custom(FlickerCurve),
+}
+
+impl FlickerIntensity {
+    /// Creates a [FlickerIntensity::Custom] from the given function.
+    pub fn custom(f: impl Fn(f32) -> f32 + Send + Sync + 'static) -> Self {
+        Self::Custom(FlickerCurve::new(f))
+    }
+
+    /// Samples the intensity at the given progress, between 0.0 and 1.0.
+    pub fn sample(&self, progress: f32) -> f32 {
+        let t = progress.clamp(0.0, 1.0);
+        let intensity = match self {
+            Self::Constant => 1.0,
+            Self::FadeOut => 1.0 - t,
+            Self::FadeIn => t,
+            Self::EaseInOut => EaseFunction::SmoothStep.sample_clamped(1.0 - (2.0 * t - 1.0).abs()),
+            Self::Ease(ease) => ease.sample_clamped(t),
+            Self::Adsr {
+                attack,
+                decay,
+                sustain,
+                release,
+            } => {
+                if t < *attack {
+                    t / attack
+                } else if t < attack + decay {
+                    1.0 - (1.0 - sustain) * (t - attack) / decay
+                } else if t < 1.0 - release {
+                    *sustain
+                } else if *release > 0.0 {
+                    sustain * (1.0 - t) / release
+                } else {
+                    *sustain
+                }
+            }
+            Self::Custom(curve) => (curve.0)(t),
+        };
+        intensity.clamp(0.0, 1.0)
+    }
+}
+
+/// A user-supplied intensity curve, receives the progress of the flicker between 0.0 and 1.0
+/// and returns the intensity between 0.0 and 1.0.
+#[derive(Clone, Reflect)]
+#[reflect(opaque)]
+pub struct FlickerCurve(Arc<dyn Fn(f32) -> f32 + Send + Sync>);
+
+impl FlickerCurve {
+    pub fn new(f: impl Fn(f32) -> f32 + Send + Sync + 'static) -> Self {
+        Self(Arc::new(f))
+    }
+}
+
+impl fmt::Debug for FlickerCurve {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("FlickerCurve").finish_non_exhaustive()
+    }
+}
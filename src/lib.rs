@@ -1,10 +1,48 @@
 //! This plugin facilitates creating a brief overlay/mix of a specific color over a sprite or mesh.
+//! Both 2D meshes and 3D meshes using a `StandardMaterial` can be flickered, 3D flickers keep the
+//! lighting of the underlying material. 3D support comes from the `3d` feature, which is on by
+//! default and needs bevy's `PbrPlugin`. Apps built with only bevy's 2D features should turn off
+//! default features.
+//!
+//! With the `ui` feature, UI nodes can be flickered as well. Image nodes and nodes with a
+//! background or border color get an overlay node drawn over them, while text has its color
+//! tinted for the length of the flicker and then put back. The `text` feature does the same for
+//! world-space `Text2d`, and is included in the `ui` feature.
 //!
 //! To trigger a flicker, you can send a [FlickerStartEvent][events::FlickerStartEvent], which will contain the parameters
 //! that dictate the color, length, and strength of the flicker.
 //!
+//! When a flicker starts, ends, or is cut short, a [FlickerStarted][events::FlickerStarted],
+//! [FlickerEnded][events::FlickerEnded], or [FlickerInterrupted][events::FlickerInterrupted] is
+//! sent. These can be read as messages or observed on the flickered entity.
+//!
+//! An active flicker can be stopped early by sending a
+//! [FlickerStopEvent][events::FlickerStopEvent], or through
+//! [FlickerCommandsExt][commands::FlickerCommandsExt].
+//!
 //! Included is also a [RepeatingFlicker][components::RepeatingFlicker] component that will send
-//! [FlickerStartEvents][events::FlickerStartEvent] on an interval.
+//! [FlickerStartEvents][events::FlickerStartEvent] on an interval, and a
+//! [Flicker][components::Flicker] component that flickers an entity once when it is inserted,
+//! such as when spawning it.
+//!
+//! The strength of a flicker can change over its lifetime, such as fading out, by setting a
+//! [FlickerIntensity][intensity::FlickerIntensity] on the event, and it can move through a sequence
+//! of colors by setting a [FlickerGradient][gradient::FlickerGradient]. How the color is combined
+//! with the sprite is controlled by its [FlickerBlendMode][blend::FlickerBlendMode], and which
+//! part of the sprite it covers by its [FlickerStyle][style::FlickerStyle].
+//!
+//! Flickers advance with virtual time by default, so they pause and slow down along with the
+//! game. The clock can be changed to real time with
+//! [FlickerTimeSource][config::FlickerTimeSource], and sped up or slowed down for a single
+//! entity with [FlickerTimeScale][components::FlickerTimeScale]. A single entity's flickers can
+//! be frozen in place with [FlickerPaused][components::FlickerPaused].
+//!
+//! Instead of drawing an overlay, a flicker can change the color of the entity itself for its
+//! length by setting its [FlickerMode][mode::FlickerMode] to
+//! [Tint][mode::FlickerMode::Tint].
+//!
+//! 2D overlays can be drawn with your own material in place of [FlickerMaterial] by implementing
+//! [FlickerShader][shader::FlickerShader] for it, see the [shader] module.
 //!
 //! This also works on textures with alpha, the overlay takes into account the alpha of the underlying texture
 //! and will adjust the overlay alpha so that it's intensity is proportional between different underlying
@@ -32,32 +70,166 @@
 //! ```
 
 use bevy_app::{App, Plugin, Update};
-use bevy_ecs::schedule::{IntoScheduleConfigs, SystemSet};
+use bevy_ecs::schedule::{InternedScheduleLabel, IntoScheduleConfigs, ScheduleLabel, SystemSet};
 
+use bevy_shader::load_shader_library;
 use bevy_sprite_render::Material2dPlugin;
+use bevy_transform::TransformSystems;
 
+pub mod blend;
+mod clock;
+pub mod commands;
 pub mod components;
 pub mod config;
 pub mod events;
 mod flicker;
+pub mod gradient;
+pub mod intensity;
+pub mod mode;
+mod overlay;
+pub mod shader;
+mod sprite_shape;
+pub mod style;
 mod systems;
+mod tint;
 
+use components::{
+    Flicker, FlickerGroup, FlickerGroupMember, FlickerMarker, FlickerOverlay, FlickerQueue,
+    Flickered, PersistentOverlay, RepeatingFlicker,
+};
 use config::FlickerPluginConfig;
-use events::FlickerStartEvent;
-use flicker::FlickerMaterial;
-use systems::{flicker_start, flicker_tick, repeating_flicker_tick};
+use events::{
+    FlickerEnded, FlickerInterrupted, FlickerPendingStarts, FlickerStartEvent, FlickerStarted,
+    FlickerStopEvent,
+};
+use overlay::FlickerAssetCache;
+use shader::FlickerShader;
+use systems::{
+    flicker_sprite_sync, flicker_start, flicker_stop, flicker_tick, inserted_flicker_start,
+    recycle_overlay_material, repeating_flicker_tick, restore_tint,
+};
+
+#[cfg(feature = "3d")]
+use bevy_pbr::MaterialPlugin;
+#[cfg(feature = "ui")]
+use bevy_ui_render::prelude::UiMaterialPlugin;
+#[cfg(feature = "3d")]
+use flicker::FlickerMaterial3d;
+#[cfg(feature = "ui")]
+use flicker::FlickerUiMaterial;
+
+pub use flicker::FlickerMaterial;
+pub use tint::FlickerTint;
 
+use std::hash::Hash;
+use std::marker::PhantomData;
 use std::path::{Path, PathBuf};
 
-/// The bevy plugin to include during App initialization
+/// The bevy plugin to include during App initialization.
+///
+/// See [FlickerPlugin::with_material] to draw 2D overlays with a material other than
+/// [FlickerMaterial], and [FlickerPlugin::with_schedule] to run the flicker systems outside of
+/// `Update`.
+///
+/// With the `3d` feature, the app needs bevy's `PbrPlugin` as well, such as through
+/// `DefaultPlugins`.
 #[derive(Default)]
 pub struct FlickerPlugin;
 
+impl FlickerPlugin {
+    /// Creates the plugin with 2D overlays drawn by the given material instead of
+    /// [FlickerMaterial], see the [shader] module.
+    pub fn with_material<M: FlickerShader>() -> CustomFlickerPlugin<M> {
+        CustomFlickerPlugin {
+            schedule: Update.intern(),
+            material: PhantomData,
+        }
+    }
+
+    /// Runs the flicker systems in the given schedule instead of `Update`.
+    ///
+    /// In `FixedUpdate`, flickers advance by exactly one timestep each run when using
+    /// [FlickerTimeSource::Virtual][config::FlickerTimeSource::Virtual], so the state of a
+    /// flicker only depends on the number of ticks it has been running for. This makes flickers
+    /// replay identically, such as when re-simulating for rollback networking. The flicker state
+    /// to snapshot is made up of these components, along with the overlay entities they are on,
+    /// and the [FlickerPendingStarts] resource:
+    ///
+    /// - [Flicker][components::Flicker], [RepeatingFlicker][components::RepeatingFlicker],
+    ///   [FlickerQueue][components::FlickerQueue] and [FlickerMarker][components::FlickerMarker]
+    ///   on flickered entities
+    /// - [Flickered][components::Flickered] on overlays
+    /// - [FlickerGroup][components::FlickerGroup] and
+    ///   [FlickerGroupMember][components::FlickerGroupMember] for recursive flickers
+    /// - [FlickerOverlay][components::FlickerOverlay] and
+    ///   [PersistentOverlay][components::PersistentOverlay] for persistent overlays
+    /// - [FlickerTint] for flickers that change the color of the entity itself
+    ///
+    /// All of them are registered for reflection.
+    pub fn with_schedule(self, schedule: impl ScheduleLabel) -> CustomFlickerPlugin {
+        Self::with_material().with_schedule(schedule)
+    }
+}
+
+impl Plugin for FlickerPlugin {
+    fn build(&self, app: &mut App) {
+        CustomFlickerPlugin::<FlickerMaterial>::default().build(app);
+    }
+}
+
+/// [FlickerPlugin] with its 2D material or schedule changed, created through
+/// [FlickerPlugin::with_material] or [FlickerPlugin::with_schedule]
+pub struct CustomFlickerPlugin<M = FlickerMaterial> {
+    schedule: InternedScheduleLabel,
+    material: PhantomData<M>,
+}
+
+impl Default for CustomFlickerPlugin {
+    fn default() -> Self {
+        FlickerPlugin::with_material()
+    }
+}
+
+impl<M> CustomFlickerPlugin<M> {
+    /// Runs the flicker systems in the given schedule instead of `Update`, see
+    /// [FlickerPlugin::with_schedule]
+    pub fn with_schedule(mut self, schedule: impl ScheduleLabel) -> Self {
+        self.schedule = schedule.intern();
+        self
+    }
+}
+
 /// The SystemSet that the flicker systems belong to.
+///
+/// It runs before transforms are propagated, so a flicker started before it in the same frame
+/// is drawn that frame. Within it, the systems run in the order of [FlickerSystems].
 #[derive(SystemSet, Debug, Hash, PartialEq, Eq, Clone, Copy)]
 pub struct FlickerSet;
 
-impl Plugin for FlickerPlugin {
+/// The steps of [FlickerSet], which run one after another in the order listed
+#[derive(SystemSet, Debug, Hash, PartialEq, Eq, Clone, Copy)]
+pub enum FlickerSystems {
+    /// [RepeatingFlickers][components::RepeatingFlicker] and [Flickers][components::Flicker]
+    /// send their [FlickerStartEvents][events::FlickerStartEvent]
+    Repeat,
+
+    /// [FlickerStopEvents][events::FlickerStopEvent] and then
+    /// [FlickerStartEvents][events::FlickerStartEvent] are handled, spawning the overlays of new
+    /// flickers
+    Start,
+
+    /// Flickers advance, and the ones that finished are taken down
+    Tick,
+
+    /// Overlays are kept in step with their sprites, such as when an animation moves on to the
+    /// next frame
+    Sync,
+}
+
+impl<M: FlickerShader> Plugin for CustomFlickerPlugin<M>
+where
+    M::Data: PartialEq + Eq + Hash + Clone,
+{
     fn build(&self, app: &mut App) {
         // Register the flicker mateiral as an internal asset
         let embedded = app
@@ -66,30 +238,102 @@ impl Plugin for FlickerPlugin {
         let path = Path::new("flicker_material.wgsl");
         embedded.insert_asset(
             PathBuf::new(),
-            &path,
+            path,
             include_bytes!("flicker_material.wgsl"),
         );
 
-        app.add_plugins(Material2dPlugin::<FlickerMaterial>::default())
+        #[cfg(feature = "3d")]
+        embedded.insert_asset(
+            PathBuf::new(),
+            Path::new("flicker_material_3d.wgsl"),
+            include_bytes!("flicker_material_3d.wgsl"),
+        );
+        #[cfg(feature = "ui")]
+        embedded.insert_asset(
+            PathBuf::new(),
+            Path::new("flicker_ui_material.wgsl"),
+            include_bytes!("flicker_ui_material.wgsl"),
+        );
+        load_shader_library!(app, "flicker_blend.wgsl");
+
+        app.add_plugins(Material2dPlugin::<M>::default())
             .register_type::<FlickerMaterial>();
+        // The flicker state, see FlickerPlugin::with_schedule
+        app.register_type::<Flicker>()
+            .register_type::<RepeatingFlicker>()
+            .register_type::<FlickerQueue>()
+            .register_type::<FlickerMarker>()
+            .register_type::<Flickered>()
+            .register_type::<FlickerGroup>()
+            .register_type::<FlickerGroupMember>()
+            .register_type::<FlickerOverlay>()
+            .register_type::<PersistentOverlay>()
+            .register_type::<FlickerTint>()
+            .register_type::<FlickerPendingStarts>();
+        #[cfg(feature = "3d")]
+        app.add_plugins(MaterialPlugin::<FlickerMaterial3d>::default());
+        #[cfg(feature = "ui")]
+        app.add_plugins(UiMaterialPlugin::<FlickerUiMaterial>::default());
 
         // Register events
-        app.add_message::<FlickerStartEvent>();
+        app.add_message::<FlickerStartEvent>()
+            .add_message::<FlickerStopEvent>()
+            .add_message::<FlickerStarted>()
+            .add_message::<FlickerEnded>()
+            .add_message::<FlickerInterrupted>();
 
         // Register systems and systemset
-        // TODO: These might need to be ordered to prevent conflicts potentially?
-        app.add_systems(Update, flicker_start.in_set(FlickerSet));
-        app.add_systems(Update, flicker_tick.in_set(FlickerSet));
-        app.add_systems(Update, repeating_flicker_tick.in_set(FlickerSet));
+        let schedule = self.schedule;
+        app.configure_sets(
+            schedule,
+            (
+                FlickerSystems::Repeat,
+                FlickerSystems::Start,
+                FlickerSystems::Tick,
+                FlickerSystems::Sync,
+            )
+                .chain()
+                .in_set(FlickerSet),
+        );
+        app.configure_sets(schedule, FlickerSet.before(TransformSystems::Propagate));
+        app.add_systems(
+            schedule,
+            (repeating_flicker_tick, inserted_flicker_start).in_set(FlickerSystems::Repeat),
+        );
+        app.add_systems(
+            schedule,
+            (flicker_stop, flicker_start::<M>)
+                .chain()
+                .in_set(FlickerSystems::Start),
+        );
+        app.add_systems(schedule, flicker_tick::<M>.in_set(FlickerSystems::Tick));
+        app.add_systems(
+            schedule,
+            flicker_sprite_sync::<M>.in_set(FlickerSystems::Sync),
+        );
+        app.add_observer(recycle_overlay_material::<M>);
+        app.add_observer(restore_tint);
         app.init_resource::<FlickerPluginConfig>();
+        app.init_resource::<FlickerAssetCache<M>>();
+        app.init_resource::<FlickerPendingStarts>();
     }
 }
 
 pub mod prelude {
     pub use super::{
-        components::RepeatingFlicker,
-        config::{FlickerOverlapAction, FlickerPluginConfig},
+        blend::FlickerBlendMode,
+        commands::FlickerCommandsExt,
+        components::{
+            Flicker, FlickerOnSpawn, FlickerOverlapOverride, FlickerOverlay, FlickerPaused,
+            FlickerTimeScale, FlickerTimeSourceOverride, NoFlicker, RepeatingFlicker,
+        },
+        config::{FlickerOverlapAction, FlickerPluginConfig, FlickerTimeSource},
         events::*,
-        FlickerPlugin, FlickerSet,
+        gradient::{FlickerColorSpace, FlickerGradient},
+        intensity::FlickerIntensity,
+        mode::FlickerMode,
+        shader::{FlickerInputs, FlickerShader},
+        style::FlickerStyle,
+        CustomFlickerPlugin, FlickerPlugin, FlickerSet, FlickerSystems,
     };
 }
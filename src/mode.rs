@@ -0,0 +1,23 @@
+use bevy_reflect::Reflect;
+
+/// How a flicker is shown on the flickered entity.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Reflect)]
+pub enum FlickerMode {
+    /// Draws the flicker on an overlay entity spawned as a child of the flickered entity, leaving
+    /// the entity itself untouched.
+    #[default]
+    Overlay,
+
+    /// Changes the color of the flickered entity itself and puts it back once the flicker is
+    /// over. This avoids the overlay fighting with sprites placed just in front of the entity,
+    /// and keeps the flicker on the same render layers and post-processing as the entity.
+    ///
+    /// Works on the color of a `Sprite`, and on a 2D mesh using a `ColorMaterial`, whose material
+    /// is swapped for a tinted copy so that other entities sharing it are not affected. Entities
+    /// that cannot be tinted fall back to an overlay.
+    ///
+    /// The blend mode is applied to the color of the entity rather than to each of its pixels,
+    /// and the texture is still multiplied by the result, so a tinted flicker can only darken or
+    /// recolor a texture where an overlay could also brighten it.
+    Tint,
+}
@@ -0,0 +1,1059 @@
+use crate::{
+    blend::FlickerBlendMode,
+    components::{FlickerGroup, FlickerGroupMember, Flickered, NoFlicker, PersistentOverlay},
+    mode::FlickerMode,
+    shader::{FlickerInputs, FlickerShader},
+    sprite_shape::{self, SpriteMesh},
+    style::FlickerStyle,
+    tint::{FlickerTint, TintOriginal},
+};
+
+use bevy_asset::{AssetId, Assets, Handle, UntypedAssetId};
+use bevy_camera::visibility::{RenderLayers, Visibility};
+use bevy_color::LinearRgba;
+use bevy_ecs::{
+    entity::Entity,
+    hierarchy::Children,
+    query::{Changed, Has, Or, QueryData, With, Without},
+    resource::Resource,
+    system::{EntityCommands, Query, Res, ResMut, SystemParam},
+};
+use bevy_image::{Image, TextureAtlasLayout};
+use bevy_log::error;
+use bevy_math::{Rect, URect, Vec2, Vec3};
+use bevy_mesh::{Mesh, Mesh2d};
+use bevy_sprite::{Anchor, Sprite};
+use bevy_sprite_render::{ColorMaterial, MeshMaterial2d};
+use bevy_transform::components::Transform;
+
+#[cfg(feature = "ui")]
+use crate::flicker::FlickerUiMaterial;
+#[cfg(feature = "3d")]
+use crate::flicker::{FlickerExtension, FlickerMaterial3d};
+#[cfg(feature = "ui")]
+use bevy_color::Alpha;
+#[cfg(feature = "3d")]
+use bevy_mesh::Mesh3d;
+#[cfg(feature = "3d")]
+use bevy_pbr::{MeshMaterial3d, StandardMaterial};
+#[cfg(feature = "ui")]
+use bevy_picking::Pickable;
+#[cfg(feature = "3d")]
+use bevy_render::alpha::AlphaMode;
+#[cfg(feature = "text")]
+use bevy_text::{TextColor, TextSpan};
+#[cfg(feature = "ui")]
+use bevy_ui::{
+    widget::ImageNode, BackgroundColor, BorderColor, ComputedNode, FocusPolicy, Node, PositionType,
+    UiRect, Val,
+};
+#[cfg(feature = "ui")]
+use bevy_ui_render::prelude::MaterialNode;
+
+use std::collections::HashMap;
+
+/// How far in front of each other stacked 2D overlays are placed
+const STACK_LAYER_DEPTH: f32 = 0.01;
+
+/// The material of an overlay entity, only one of which will be present
+#[derive(QueryData)]
+pub(crate) struct OverlayMaterials<M: FlickerShader> {
+    material_2d: Option<&'static MeshMaterial2d<M>>,
+    #[cfg(feature = "3d")]
+    material_3d: Option<&'static MeshMaterial3d<FlickerMaterial3d>>,
+    #[cfg(feature = "ui")]
+    material_ui: Option<&'static MaterialNode<FlickerUiMaterial>>,
+}
+
+// Derived impls would require the material itself to be Clone, Copy and Default, while the item
+// only holds references to it.
+impl<M: FlickerShader> Clone for OverlayMaterialsItem<'_, '_, M> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<M: FlickerShader> Copy for OverlayMaterialsItem<'_, '_, M> {}
+
+impl<M: FlickerShader> Default for OverlayMaterialsItem<'_, '_, M> {
+    fn default() -> Self {
+        Self {
+            material_2d: None,
+            #[cfg(feature = "3d")]
+            material_3d: None,
+            #[cfg(feature = "ui")]
+            material_ui: None,
+        }
+    }
+}
+
+/// Whether an overlay entity is kept alive between flickers, and the overlays of the descendants
+/// it flickers along with it
+pub(crate) type OverlayState<'a> = (Has<PersistentOverlay>, Option<&'a FlickerGroup>);
+
+/// Matches the overlay entities spawned for flickers
+pub(crate) type IsOverlay = Or<(
+    With<Flickered>,
+    With<FlickerGroupMember>,
+    With<PersistentOverlay>,
+)>;
+
+/// Matches the overlays of flickers that are currently running
+pub(crate) type IsActiveOverlay = Or<(With<Flickered>, With<FlickerGroupMember>)>;
+
+/// Matches sprites whose overlays need to be updated to match them
+pub(crate) type SpriteChanged = Or<(Changed<Sprite>, Changed<Anchor>)>;
+
+/// The rendered part of the overlay that is spawned for a flicker
+pub(crate) enum Overlay {
+    Sprite(FlickerInputs, SpriteMesh),
+    Mesh2d(FlickerInputs, Handle<Mesh>),
+    #[cfg(feature = "3d")]
+    Mesh3d(Box<FlickerMaterial3d>, Handle<Mesh>),
+    #[cfg(feature = "ui")]
+    Ui(FlickerUiMaterial, Box<Node>),
+    /// Recolors the target itself, along with the color of the flicker it starts out with
+    Tint(FlickerTint, LinearRgba),
+}
+
+/// Identifies 2D overlay materials whose [FlickerInputs] only differ by color and progress, so
+/// that one can be reused in place of another.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+struct MaterialKey {
+    image: Option<AssetId<Image>>,
+    rect: [u32; 4],
+    blend_mode: FlickerBlendMode,
+    style: [u32; 3],
+    noise_image: Option<AssetId<Image>>,
+}
+
+impl MaterialKey {
+    fn new(inputs: &FlickerInputs) -> Self {
+        let [a, b] = inputs.offset.to_array().map(f32::to_bits);
+        let [c, d] = inputs.size.to_array().map(f32::to_bits);
+        let (noise_image, noise_scale) = inputs.style.noise();
+        Self {
+            image: inputs.source_image.as_ref().map(Handle::id),
+            rect: [a, b, c, d],
+            blend_mode: inputs.blend_mode,
+            style: [
+                inputs.style.shader_index(),
+                inputs.style.outline_thickness().to_bits(),
+                noise_scale.to_bits(),
+            ],
+            noise_image: noise_image.as_ref().map(Handle::id),
+        }
+    }
+}
+
+/// Keeps the meshes and materials of finished overlays around so that new flickers do not need
+/// to allocate their own.
+///
+/// Materials are returned to the pool once their overlay is despawned, so the pool only grows as
+/// large as the most overlays that were alive at once.
+#[derive(Resource)]
+pub(crate) struct FlickerAssetCache<M: FlickerShader> {
+    quads: HashMap<[u32; 4], Handle<Mesh>>,
+    materials: HashMap<MaterialKey, Vec<Handle<M>>>,
+
+    /// The inputs each material was last given, so that materials are only touched when their
+    /// inputs change
+    inputs: HashMap<AssetId<M>, FlickerInputs>,
+}
+
+impl<M: FlickerShader> Default for FlickerAssetCache<M> {
+    fn default() -> Self {
+        Self {
+            quads: HashMap::new(),
+            materials: HashMap::new(),
+            inputs: HashMap::new(),
+        }
+    }
+}
+
+impl<M: FlickerShader> FlickerAssetCache<M> {
+    /// Returns the material of a despawned overlay to the pool
+    pub(crate) fn recycle(&mut self, handle: Handle<M>) {
+        if let Some(inputs) = self.inputs.get(&handle.id()) {
+            self.materials
+                .entry(MaterialKey::new(inputs))
+                .or_default()
+                .push(handle);
+        }
+    }
+}
+
+/// Everything needed to work out what an overlay should look like for a flickered entity
+#[derive(SystemParam)]
+pub(crate) struct OverlaySources<'w, 's> {
+    sprites: Query<'w, 's, (&'static Sprite, &'static Anchor), Without<NoFlicker>>,
+    mesh_components: Query<'w, 's, &'static Mesh2d, Without<NoFlicker>>,
+    #[cfg(feature = "3d")]
+    meshes_3d: Query<
+        'w,
+        's,
+        (&'static Mesh3d, &'static MeshMaterial3d<StandardMaterial>),
+        Without<NoFlicker>,
+    >,
+    no_flicker: Query<'w, 's, (), With<NoFlicker>>,
+    #[cfg(feature = "ui")]
+    image_nodes: Query<
+        'w,
+        's,
+        (&'static ImageNode, &'static ComputedNode, &'static Node),
+        Without<NoFlicker>,
+    >,
+    #[cfg(feature = "ui")]
+    ui_nodes: Query<
+        'w,
+        's,
+        (
+            &'static ComputedNode,
+            &'static Node,
+            &'static BackgroundColor,
+            &'static BorderColor,
+        ),
+        Without<NoFlicker>,
+    >,
+    color_materials: Query<'w, 's, &'static MeshMaterial2d<ColorMaterial>, Without<NoFlicker>>,
+    #[cfg(feature = "text")]
+    text_colors: Query<'w, 's, &'static TextColor, Without<NoFlicker>>,
+    #[cfg(feature = "text")]
+    spans: Query<'w, 's, (), (With<TextSpan>, Without<NoFlicker>)>,
+    with_children: Query<'w, 's, &'static Children>,
+    tints: Query<'w, 's, &'static FlickerTint>,
+    render_layers: Query<'w, 's, &'static RenderLayers>,
+    images: Res<'w, Assets<Image>>,
+    atlas_layouts: Res<'w, Assets<TextureAtlasLayout>>,
+    /// Only present when the app renders 3D, see [FlickerPlugin][crate::FlickerPlugin]
+    #[cfg(feature = "3d")]
+    standard_materials: Option<Res<'w, Assets<StandardMaterial>>>,
+}
+
+impl OverlaySources<'_, '_> {
+    /// Whether the entity has anything an overlay can be created for
+    pub(crate) fn is_flickerable(&self, entity: Entity) -> bool {
+        self.sprites.contains(entity)
+            || self.mesh_components.contains(entity)
+            || self.is_flickerable_3d(entity)
+            || self.is_flickerable_ui(entity)
+            || self.is_tintable(entity)
+    }
+
+    #[cfg(feature = "3d")]
+    fn is_flickerable_3d(&self, entity: Entity) -> bool {
+        self.meshes_3d.contains(entity)
+    }
+
+    #[cfg(not(feature = "3d"))]
+    fn is_flickerable_3d(&self, _entity: Entity) -> bool {
+        false
+    }
+
+    #[cfg(feature = "ui")]
+    fn is_flickerable_ui(&self, entity: Entity) -> bool {
+        self.image_nodes.contains(entity) || self.node_colors(entity).is_some()
+    }
+
+    #[cfg(not(feature = "ui"))]
+    fn is_flickerable_ui(&self, _entity: Entity) -> bool {
+        false
+    }
+
+    #[cfg(feature = "text")]
+    fn is_tintable(&self, entity: Entity) -> bool {
+        self.text_colors.contains(entity)
+    }
+
+    #[cfg(not(feature = "text"))]
+    fn is_tintable(&self, _entity: Entity) -> bool {
+        false
+    }
+
+    /// The spans making up the rest of a text entity's text, such as the " CRIT" of "-12" and
+    /// " CRIT" drawn in different colors. They are children of the text and are tinted along
+    /// with it.
+    #[cfg(feature = "text")]
+    pub(crate) fn text_spans(&self, entity: Entity) -> Vec<Entity> {
+        let mut spans = Vec::new();
+        if !self.is_tintable(entity) {
+            return spans;
+        }
+        let mut stack = vec![entity];
+        while let Some(parent) = stack.pop() {
+            let Ok(children) = self.with_children.get(parent) else {
+                continue;
+            };
+            for &child in children.iter() {
+                if self.spans.contains(child) {
+                    spans.push(child);
+                    stack.push(child);
+                }
+            }
+        }
+        spans
+    }
+
+    #[cfg(not(feature = "text"))]
+    pub(crate) fn text_spans(&self, _entity: Entity) -> Vec<Entity> {
+        Vec::new()
+    }
+
+    /// Copies the render layers of the flickered entity onto its overlay, so that the overlay is
+    /// drawn by the same cameras as the entity
+    pub(crate) fn copy_render_layers(&self, target: Entity, overlay: &mut EntityCommands) {
+        match self.render_layers.get(target) {
+            Ok(render_layers) => overlay.insert(render_layers.clone()),
+            // A reused overlay may still have the layers of an earlier flicker
+            Err(_) => overlay.remove::<RenderLayers>(),
+        };
+    }
+
+    /// The images and meshes the overlay of the entity, and those of its descendants when
+    /// `recursive`, are drawn from that have not loaded yet. Empty once the entity can be drawn.
+    pub(crate) fn unloaded_assets(
+        &self,
+        entity: Entity,
+        recursive: bool,
+        meshes: &Assets<Mesh>,
+    ) -> Vec<UntypedAssetId> {
+        let mut unloaded = Vec::new();
+        let mut stack = vec![entity];
+        while let Some(entity) = stack.pop() {
+            self.entity_unloaded_assets(entity, meshes, &mut unloaded);
+            if recursive {
+                stack.extend(
+                    self.with_children
+                        .get(entity)
+                        .into_iter()
+                        .flat_map(|children| children.iter()),
+                );
+            }
+        }
+        unloaded
+    }
+
+    fn entity_unloaded_assets(
+        &self,
+        entity: Entity,
+        meshes: &Assets<Mesh>,
+        unloaded: &mut Vec<UntypedAssetId>,
+    ) {
+        if let Ok((sprite, _)) = self.sprites.get(entity) {
+            if !self.images.contains(&sprite.image) {
+                unloaded.push(sprite.image.id().untyped());
+            }
+            if let Some(atlas) = sprite.texture_atlas.as_ref() {
+                if !self.atlas_layouts.contains(&atlas.layout) {
+                    unloaded.push(atlas.layout.id().untyped());
+                }
+            }
+        } else if let Ok(mesh_handle) = self.mesh_components.get(entity) {
+            if !meshes.contains(&mesh_handle.0) {
+                unloaded.push(mesh_handle.0.id().untyped());
+            }
+        } else {
+            self.mesh_3d_unloaded_assets(entity, meshes, unloaded);
+            self.ui_unloaded_assets(entity, unloaded);
+        }
+    }
+
+    #[cfg(feature = "3d")]
+    fn mesh_3d_unloaded_assets(
+        &self,
+        entity: Entity,
+        meshes: &Assets<Mesh>,
+        unloaded: &mut Vec<UntypedAssetId>,
+    ) {
+        if let Ok((mesh_handle, material_handle)) = self.meshes_3d.get(entity) {
+            if !meshes.contains(&mesh_handle.0) {
+                unloaded.push(mesh_handle.0.id().untyped());
+            }
+            if let Some(materials) = self.standard_materials.as_ref() {
+                if !materials.contains(&material_handle.0) {
+                    unloaded.push(material_handle.0.id().untyped());
+                }
+            }
+        }
+    }
+
+    #[cfg(not(feature = "3d"))]
+    fn mesh_3d_unloaded_assets(
+        &self,
+        _entity: Entity,
+        _meshes: &Assets<Mesh>,
+        _unloaded: &mut Vec<UntypedAssetId>,
+    ) {
+    }
+
+    #[cfg(feature = "ui")]
+    fn ui_unloaded_assets(&self, entity: Entity, unloaded: &mut Vec<UntypedAssetId>) {
+        if let Ok((image_node, ..)) = self.image_nodes.get(entity) {
+            if !self.images.contains(&image_node.image) {
+                unloaded.push(image_node.image.id().untyped());
+            }
+        }
+    }
+
+    #[cfg(not(feature = "ui"))]
+    fn ui_unloaded_assets(&self, _entity: Entity, _unloaded: &mut Vec<UntypedAssetId>) {}
+
+    /// Whether the entity has opted out of flickering
+    pub(crate) fn is_no_flicker(&self, entity: Entity) -> bool {
+        self.no_flicker.contains(entity)
+    }
+
+    /// Creates the overlay for the given entity, returns None if the entity cannot currently
+    /// be flickered.
+    pub(crate) fn overlay(
+        &self,
+        entity: Entity,
+        flickered: &Flickered,
+        blend_mode: FlickerBlendMode,
+        meshes: &Assets<Mesh>,
+    ) -> Option<Overlay> {
+        if flickered.event.mode == FlickerMode::Tint {
+            // Anything that cannot be tinted falls back to an overlay
+            if let Some(tint) = self.tint_overlay(entity, flickered, blend_mode) {
+                return Some(tint);
+            }
+        }
+        let overlay = if let Ok((sprite, anchor)) = self.sprites.get(entity) {
+            let (geometry, mesh) = self.sprite_geometry(sprite, anchor)?;
+            Overlay::Sprite(
+                FlickerInputs {
+                    color: flickered.current_color(),
+                    progress: flickered.progress(),
+                    blend_mode,
+                    style: flickered.event.style.clone(),
+                    ..geometry
+                },
+                mesh,
+            )
+        } else if let Ok(mesh_handle) = self.mesh_components.get(entity) {
+            if meshes.contains(&mesh_handle.0) {
+                // The overlay shares the entity's own mesh
+                Overlay::Mesh2d(
+                    FlickerInputs {
+                        color: flickered.current_color(),
+                        progress: flickered.progress(),
+                        blend_mode,
+                        style: FlickerStyle::Fill,
+                        ..Default::default()
+                    },
+                    mesh_handle.0.clone(),
+                )
+            } else {
+                error!("Entity {:?} had an invalid mesh handle", entity);
+                return None;
+            }
+        } else {
+            return self
+                .mesh_3d_overlay(entity, flickered, blend_mode)
+                .or_else(|| self.ui_overlay(entity, flickered, blend_mode))
+                .or_else(|| self.tint_overlay(entity, flickered, blend_mode));
+        };
+        Some(overlay)
+    }
+
+    /// Creates the overlay for a 3D mesh, which reuses the entity's own mesh and material so that
+    /// it is lit the same way. The extension then applies the flicker color on top.
+    #[cfg(feature = "3d")]
+    fn mesh_3d_overlay(
+        &self,
+        entity: Entity,
+        flickered: &Flickered,
+        blend_mode: FlickerBlendMode,
+    ) -> Option<Overlay> {
+        let (mesh_handle, material_handle) = self.meshes_3d.get(entity).ok()?;
+        let Some(materials) = self.standard_materials.as_ref() else {
+            error!(
+                "Entity {:?} has a 3D mesh, which can only be flickered with bevy's PbrPlugin",
+                entity
+            );
+            return None;
+        };
+        let Some(base) = materials.get(&material_handle.0) else {
+            error!("Entity {:?} had an invalid material handle", entity);
+            return None;
+        };
+        Some(Overlay::Mesh3d(
+            Box::new(FlickerMaterial3d {
+                base: StandardMaterial {
+                    alpha_mode: AlphaMode::Blend,
+                    depth_bias: base.depth_bias + 1.0,
+                    ..base.clone()
+                },
+                extension: FlickerExtension {
+                    color: flickered.current_color(),
+                    blend_mode: blend_mode.shader_index(),
+                },
+            }),
+            mesh_handle.0.clone(),
+        ))
+    }
+
+    #[cfg(not(feature = "3d"))]
+    fn mesh_3d_overlay(
+        &self,
+        _entity: Entity,
+        _flickered: &Flickered,
+        _blend_mode: FlickerBlendMode,
+    ) -> Option<Overlay> {
+        None
+    }
+
+    /// Creates the overlay for a UI node, either its image or its background and border colors
+    /// are flickered.
+    #[cfg(feature = "ui")]
+    fn ui_overlay(
+        &self,
+        entity: Entity,
+        flickered: &Flickered,
+        blend_mode: FlickerBlendMode,
+    ) -> Option<Overlay> {
+        let color = flickered.current_color();
+        if let Ok((image_node, computed_node, node)) = self.image_nodes.get(entity) {
+            let material = self.image_node_geometry(image_node)?;
+            let image_color = image_node.color.into();
+            return Some(Overlay::Ui(
+                FlickerUiMaterial {
+                    base_color: image_color,
+                    border_colors: [image_color; 4],
+                    color,
+                    blend_mode: blend_mode.shader_index(),
+                    ..material
+                },
+                Box::new(ui_overlay_node(computed_node, node)),
+            ));
+        }
+        if let Some((base_color, border_colors, computed_node, node)) = self.node_colors(entity) {
+            return Some(Overlay::Ui(
+                FlickerUiMaterial {
+                    base_color,
+                    border_colors,
+                    color,
+                    blend_mode: blend_mode.shader_index(),
+                    ..Default::default()
+                },
+                Box::new(ui_overlay_node(computed_node, node)),
+            ));
+        }
+        None
+    }
+
+    #[cfg(not(feature = "ui"))]
+    fn ui_overlay(
+        &self,
+        _entity: Entity,
+        _flickered: &Flickered,
+        _blend_mode: FlickerBlendMode,
+    ) -> Option<Overlay> {
+        None
+    }
+
+    /// Creates the tint for an entity whose own color is changed rather than getting an overlay,
+    /// which is either a sprite or 2D mesh with [FlickerMode::Tint], or text, as there is nothing
+    /// an overlay can be drawn over.
+    fn tint_overlay(
+        &self,
+        entity: Entity,
+        flickered: &Flickered,
+        blend_mode: FlickerBlendMode,
+    ) -> Option<Overlay> {
+        // When the entity is already tinted by another flicker its current color is not its
+        // own, so start from what that flicker will put back instead.
+        let existing = self
+            .with_children
+            .get(entity)
+            .into_iter()
+            .flat_map(|children| children.iter())
+            .filter_map(|child| self.tints.get(*child).ok())
+            .find(|tint| tint.target == entity)
+            .map(|tint| tint.original.clone());
+        let original = if let Some(existing) = existing {
+            existing
+        } else if let Ok((sprite, _)) = self.sprites.get(entity) {
+            TintOriginal::Sprite(sprite.color)
+        } else if let Ok(material) = self.color_materials.get(entity) {
+            TintOriginal::ColorMaterial(material.0.clone())
+        } else {
+            self.text_original(entity)?
+        };
+        Some(Overlay::Tint(
+            FlickerTint {
+                target: entity,
+                original,
+                blend_mode,
+                material: None,
+            },
+            flickered.current_color(),
+        ))
+    }
+
+    #[cfg(feature = "text")]
+    fn text_original(&self, entity: Entity) -> Option<TintOriginal> {
+        let text_color = self.text_colors.get(entity).ok()?;
+        Some(TintOriginal::Text(text_color.0))
+    }
+
+    #[cfg(not(feature = "text"))]
+    fn text_original(&self, _entity: Entity) -> Option<TintOriginal> {
+        None
+    }
+
+    /// The colors of a node with a visible background or border, in the order of the fields of
+    /// [FlickerUiMaterial]
+    #[cfg(feature = "ui")]
+    fn node_colors(
+        &self,
+        entity: Entity,
+    ) -> Option<(LinearRgba, [LinearRgba; 4], &ComputedNode, &Node)> {
+        let (computed_node, node, background, border) = self.ui_nodes.get(entity).ok()?;
+        let border_colors = [border.top, border.right, border.bottom, border.left];
+        let has_border = computed_node.border().min_inset != Vec2::ZERO
+            || computed_node.border().max_inset != Vec2::ZERO;
+        let visible = !background.0.is_fully_transparent()
+            || (has_border && border_colors.iter().any(|c| !c.is_fully_transparent()));
+        visible.then(|| {
+            (
+                background.0.into(),
+                border_colors.map(LinearRgba::from),
+                computed_node,
+                node,
+            )
+        })
+    }
+
+    /// The part of the image that the image node shows, as a material without a color. Returns
+    /// None if the image node cannot currently be flickered.
+    ///
+    /// Sliced and tiled images are flickered as a plain node of the image's color.
+    #[cfg(feature = "ui")]
+    fn image_node_geometry(&self, image_node: &ImageNode) -> Option<FlickerUiMaterial> {
+        if image_node.image_mode.uses_slices() {
+            return Some(FlickerUiMaterial::default());
+        }
+        let img = if let Some(img) = self.images.get(&image_node.image) {
+            img
+        } else {
+            error!("Could not get image from image handle to begin flicker");
+            return None;
+        };
+        let image_size = img.size().as_vec2();
+
+        // The image's rect is relative to the current atlas rect when it has both
+        let atlas_rect = image_node
+            .texture_atlas
+            .as_ref()
+            .and_then(|texture_atlas| texture_atlas.texture_rect(&self.atlas_layouts))
+            .map(|rect| rect.as_rect());
+        let texture_rect = match (atlas_rect, image_node.rect) {
+            (None, None) => Rect::from_corners(Vec2::ZERO, image_size),
+            (None, Some(image_rect)) => image_rect,
+            (Some(atlas_rect), None) => atlas_rect,
+            (Some(atlas_rect), Some(image_rect)) => Rect::from_corners(
+                image_rect.min + atlas_rect.min,
+                image_rect.max + atlas_rect.min,
+            ),
+        };
+        let mut offset = texture_rect.min / image_size;
+        let mut size = texture_rect.size() / image_size;
+        if image_node.flip_x {
+            offset.x += size.x;
+            size.x *= -1.0;
+        }
+        if image_node.flip_y {
+            offset.y += size.y;
+            size.y *= -1.0;
+        }
+        Some(FlickerUiMaterial {
+            source_image: Some(image_node.image.clone()),
+            offset,
+            size,
+            ..Default::default()
+        })
+    }
+
+    /// The part of the image that the sprite shows, as inputs without a color, along with the
+    /// mesh covering the area the sprite is drawn in. Returns None if the sprite cannot currently
+    /// be flickered.
+    pub(crate) fn sprite_geometry(
+        &self,
+        sprite: &Sprite,
+        anchor: &Anchor,
+    ) -> Option<(FlickerInputs, SpriteMesh)> {
+        let image_handle = &sprite.image;
+        let img = if let Some(img) = self.images.get(image_handle) {
+            img
+        } else {
+            error!("Could not get image from image handle to begin flicker");
+            return None;
+        };
+        let image_size = img.size().as_vec2();
+
+        let atlas = if let Some(texture_atlas) = sprite.texture_atlas.as_ref() {
+            if let Some(atlas) = self.atlas_layouts.get(&texture_atlas.layout) {
+                let curr_rect = atlas
+                    .textures
+                    .get(texture_atlas.index)
+                    .copied()
+                    .unwrap_or(URect::new(0, 0, 0, 0));
+                Some((curr_rect.as_rect(), atlas.size.as_vec2()))
+            } else {
+                error!("Could not get atlas to determine which part of sprite is currently active");
+                return None;
+            }
+        } else {
+            None
+        };
+
+        if sprite.image_mode.uses_slices() {
+            // Slices are drawn from the atlas rect or the sprite's rect, but not both
+            let (texture_rect, default_size) = atlas.unwrap_or((
+                sprite
+                    .rect
+                    .unwrap_or(Rect::from_corners(Vec2::ZERO, image_size)),
+                image_size,
+            ));
+            let mesh = sprite_shape::slices(
+                sprite,
+                anchor.as_vec(),
+                image_size,
+                texture_rect,
+                default_size,
+            );
+            return Some((
+                FlickerInputs {
+                    source_image: Some(image_handle.clone()),
+                    ..Default::default()
+                },
+                mesh,
+            ));
+        }
+
+        // The sprite's rect is relative to the current atlas rect when it has both
+        let texture_rect = match (atlas.map(|(rect, _)| rect), sprite.rect) {
+            (None, None) => Rect::from_corners(Vec2::ZERO, image_size),
+            (None, Some(sprite_rect)) => sprite_rect,
+            (Some(atlas_rect), None) => atlas_rect,
+            (Some(atlas_rect), Some(sprite_rect)) => Rect::from_corners(
+                sprite_rect.min + atlas_rect.min,
+                sprite_rect.max + atlas_rect.min,
+            ),
+        };
+        let (offset, size, mesh) =
+            sprite_shape::quad(sprite, anchor.as_vec(), image_size, texture_rect);
+        Some((
+            FlickerInputs {
+                source_image: Some(image_handle.clone()),
+                offset,
+                size,
+                ..Default::default()
+            },
+            mesh,
+        ))
+    }
+}
+
+/// The node of a UI overlay. The overlay is placed inside the border of its parent like any other
+/// child, so it is pulled out over the border to cover the whole of its parent and given the same
+/// border so that the material can tell where it is.
+#[cfg(feature = "ui")]
+fn ui_overlay_node(computed_node: &ComputedNode, node: &Node) -> Node {
+    // The computed border is in physical pixels, while nodes are laid out in logical pixels
+    let border = computed_node.border();
+    let min = border.min_inset * computed_node.inverse_scale_factor();
+    let max = border.max_inset * computed_node.inverse_scale_factor();
+    Node {
+        position_type: PositionType::Absolute,
+        left: Val::Px(-min.x),
+        top: Val::Px(-min.y),
+        right: Val::Px(-max.x),
+        bottom: Val::Px(-max.y),
+        border: UiRect {
+            left: Val::Px(min.x),
+            right: Val::Px(max.x),
+            top: Val::Px(min.y),
+            bottom: Val::Px(max.y),
+        },
+        border_radius: node.border_radius,
+        ..Default::default()
+    }
+}
+
+/// The key of a quad in [FlickerAssetCache::quads]
+fn quad_key(size: Vec2, center: Vec2) -> [u32; 4] {
+    let [a, b] = size.to_array().map(f32::to_bits);
+    let [c, d] = center.to_array().map(f32::to_bits);
+    [a, b, c, d]
+}
+
+/// The asset stores that overlays are added to, 2D overlays are drawn with `M`
+#[derive(SystemParam)]
+pub(crate) struct OverlayAssets<'w, M: FlickerShader> {
+    pub(crate) meshes: ResMut<'w, Assets<Mesh>>,
+    pub(crate) flicker_materials: ResMut<'w, Assets<M>>,
+    #[cfg(feature = "3d")]
+    pub(crate) flicker_materials_3d: ResMut<'w, Assets<FlickerMaterial3d>>,
+    pub(crate) color_materials: ResMut<'w, Assets<ColorMaterial>>,
+    #[cfg(feature = "ui")]
+    flicker_ui_materials: ResMut<'w, Assets<FlickerUiMaterial>>,
+    cache: ResMut<'w, FlickerAssetCache<M>>,
+}
+
+impl<M: FlickerShader> OverlayAssets<'_, M> {
+    /// Adds the rendering components of the overlay to the given entity. 2D overlays are placed
+    /// `z_offset` in front of their parent, and overlays stacked on top of other overlays are
+    /// given a higher layer.
+    ///
+    /// When the entity already has a material from an earlier flicker, the new material is
+    /// written into it instead of adding another.
+    pub(crate) fn insert(
+        &mut self,
+        entity_commands: &mut EntityCommands,
+        overlay: Overlay,
+        z_offset: f32,
+        layer: usize,
+        existing: OverlayMaterialsItem<M>,
+    ) {
+        let (inputs, mesh) = match overlay {
+            Overlay::Sprite(inputs, mesh) => (inputs, self.sprite_mesh(mesh)),
+            Overlay::Mesh2d(inputs, mesh) => (inputs, mesh),
+            #[cfg(feature = "3d")]
+            Overlay::Mesh3d(mut material, mesh) => {
+                // Stacked overlays are pushed further forward than the ones below them
+                material.base.depth_bias += layer as f32;
+                let handle = match existing.material_3d {
+                    Some(existing) if self.flicker_materials_3d.contains(&existing.0) => {
+                        if let Some(asset) = self.flicker_materials_3d.get_mut(&existing.0) {
+                            *asset = *material;
+                        }
+                        existing.0.clone()
+                    }
+                    _ => self.flicker_materials_3d.add(*material),
+                };
+                // Sits exactly on top of its parent, the depth bias of the material keeps it
+                // in front.
+                entity_commands.insert((
+                    MeshMaterial3d(handle),
+                    Mesh3d(mesh),
+                    Transform::default(),
+                    Visibility::Inherited,
+                ));
+                return;
+            }
+            #[cfg(feature = "ui")]
+            Overlay::Ui(material, node) => {
+                let handle = match existing.material_ui {
+                    Some(existing) if self.flicker_ui_materials.contains(&existing.0) => {
+                        if let Some(asset) = self.flicker_ui_materials.get_mut(&existing.0) {
+                            *asset = material;
+                        }
+                        existing.0.clone()
+                    }
+                    _ => self.flicker_ui_materials.add(material),
+                };
+                // Later children are drawn over earlier ones, so the overlay is in front of its
+                // parent without needing a layer. It lets the pointer through, so a flashing
+                // button can still be hovered and clicked.
+                entity_commands.insert((
+                    MaterialNode(handle),
+                    *node,
+                    FocusPolicy::Pass,
+                    Pickable::IGNORE,
+                ));
+                return;
+            }
+            Overlay::Tint(tint, color) => {
+                self.insert_tint(entity_commands, tint, color);
+                return;
+            }
+        };
+        let handle = match existing.material_2d {
+            Some(existing) if self.flicker_materials.contains(&existing.0) => {
+                self.set_inputs(&existing.0, inputs);
+                existing.0.clone()
+            }
+            _ => self.material(inputs),
+        };
+        entity_commands.insert((
+            MeshMaterial2d(handle),
+            Mesh2d(mesh),
+            Transform {
+                // Translation is relative to its parent, so any positive offset keeps it in
+                // front of its parent.
+                translation: Vec3::new(0.0, 0.0, z_offset + layer as f32 * STACK_LAYER_DEPTH),
+                ..Default::default()
+            },
+            // Overlays follow the visibility of their parent, so hiding the flickered entity
+            // hides its flicker too
+            Visibility::Inherited,
+        ));
+    }
+
+    /// Adds the tint to the given entity and applies it to its target
+    fn insert_tint(
+        &mut self,
+        entity_commands: &mut EntityCommands,
+        mut tint: FlickerTint,
+        color: LinearRgba,
+    ) {
+        let tinted = tint.tinted(color, &self.color_materials);
+        let mut commands = entity_commands.commands();
+        let mut target = commands.entity(tint.target);
+        match &tint.original {
+            TintOriginal::Sprite(_) => {
+                target
+                    .entry::<Sprite>()
+                    .and_modify(move |mut sprite| sprite.color = tinted);
+            }
+            TintOriginal::ColorMaterial(original) => {
+                // The material may be shared with other entities, so the target is given its
+                // own copy to tint
+                let material = ColorMaterial {
+                    color: tinted,
+                    ..self
+                        .color_materials
+                        .get(original)
+                        .cloned()
+                        .unwrap_or_default()
+                };
+                let handle = self.color_materials.add(material);
+                target.insert(MeshMaterial2d(handle.clone()));
+                tint.material = Some(handle);
+            }
+            #[cfg(feature = "text")]
+            TintOriginal::Text(_) => {
+                target.insert(TextColor(tinted));
+            }
+        }
+        // A reused overlay may still have the rendering components of an earlier flicker
+        entity_commands
+            .remove::<(Mesh2d, MeshMaterial2d<M>)>()
+            .insert(tint);
+        #[cfg(feature = "3d")]
+        entity_commands.remove::<(Mesh3d, MeshMaterial3d<FlickerMaterial3d>)>();
+        #[cfg(feature = "ui")]
+        entity_commands.remove::<MaterialNode<FlickerUiMaterial>>();
+    }
+
+    /// Points a sprite overlay at the part of the image its sprite now shows and resizes it to
+    /// match, leaving the color of the flicker as it is. The material and mesh are only touched
+    /// if they changed, so changes that do not move the sprite, such as to its color, are free.
+    pub(crate) fn set_sprite_geometry(
+        &mut self,
+        entity_commands: &mut EntityCommands,
+        (material, current_mesh): (&MeshMaterial2d<M>, &Mesh2d),
+        geometry: FlickerInputs,
+        mesh: SpriteMesh,
+    ) {
+        if let Some(current) = self.cache.inputs.get(&material.0.id()) {
+            let inputs = FlickerInputs {
+                source_image: geometry.source_image,
+                offset: geometry.offset,
+                size: geometry.size,
+                ..current.clone()
+            };
+            self.set_inputs(&material.0, inputs);
+        }
+        let unchanged = match &mesh {
+            SpriteMesh::Quad { size, center } => {
+                self.cache.quads.get(&quad_key(*size, *center)) == Some(&current_mesh.0)
+            }
+            SpriteMesh::Slices(mesh) => self.meshes.get(&current_mesh.0) == Some(mesh),
+        };
+        if !unchanged {
+            entity_commands.insert(Mesh2d(self.sprite_mesh(mesh)));
+        }
+    }
+
+    /// Adds the mesh of a sprite overlay, quads are shared by every sprite overlay of the same
+    /// size and position
+    fn sprite_mesh(&mut self, mesh: SpriteMesh) -> Handle<Mesh> {
+        match mesh {
+            SpriteMesh::Quad { size, center } => {
+                let meshes = &mut self.meshes;
+                self.cache
+                    .quads
+                    .entry(quad_key(size, center))
+                    .or_insert_with(|| meshes.add(SpriteMesh::quad_mesh(size, center)))
+                    .clone()
+            }
+            SpriteMesh::Slices(mesh) => self.meshes.add(mesh),
+        }
+    }
+
+    /// Takes a matching material from the pool if there is one, otherwise adds a new one
+    fn material(&mut self, inputs: FlickerInputs) -> Handle<M> {
+        let key = MaterialKey::new(&inputs);
+        let Some(handle) = self.cache.materials.get_mut(&key).and_then(Vec::pop) else {
+            let handle = self.flicker_materials.add(M::from_inputs(&inputs));
+            self.cache.inputs.insert(handle.id(), inputs);
+            return handle;
+        };
+        self.set_inputs(&handle, inputs);
+        handle
+    }
+
+    /// Gives the material new inputs
+    fn set_inputs(&mut self, handle: &Handle<M>, inputs: FlickerInputs) {
+        // Only touch the asset when needed, modifying it causes it to be prepared again
+        if self.cache.inputs.get(&handle.id()) == Some(&inputs) {
+            return;
+        }
+        if let Some(material) = self.flicker_materials.get_mut(handle) {
+            material.set_inputs(&inputs);
+        }
+        self.cache.inputs.insert(handle.id(), inputs);
+    }
+
+    /// Sets the color of the overlay's material, along with how far along the flicker is for
+    /// materials that change as it progresses. Materials are left alone when nothing changed.
+    pub(crate) fn animate(
+        &mut self,
+        materials: OverlayMaterialsItem<M>,
+        color: LinearRgba,
+        progress: f32,
+    ) {
+        if let Some(material) = materials.material_2d {
+            if let Some(current) = self.cache.inputs.get(&material.0.id()) {
+                let progress = if M::uses_progress(current) {
+                    progress
+                } else {
+                    current.progress
+                };
+                let inputs = FlickerInputs {
+                    color,
+                    progress,
+                    ..current.clone()
+                };
+                self.set_inputs(&material.0, inputs);
+            }
+        }
+        #[cfg(feature = "3d")]
+        if let Some(material) = materials.material_3d {
+            let stale = self
+                .flicker_materials_3d
+                .get(&material.0)
+                .is_some_and(|current| current.extension.color != color);
+            if stale {
+                if let Some(current) = self.flicker_materials_3d.get_mut(&material.0) {
+                    current.extension.color = color;
+                }
+            }
+        }
+        #[cfg(feature = "ui")]
+        if let Some(material) = materials.material_ui {
+            let stale = self
+                .flicker_ui_materials
+                .get(&material.0)
+                .is_some_and(|current| current.color != color);
+            if stale {
+                if let Some(current) = self.flicker_ui_materials.get_mut(&material.0) {
+                    current.color = color;
+                }
+            }
+        }
+    }
+}
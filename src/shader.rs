@@ -0,0 +1,116 @@
+//! Custom materials for 2D overlays.
+//!
+//! Sprite and [Mesh2d][bevy_mesh::Mesh2d] overlays are drawn with
+//! [FlickerMaterial][crate::FlickerMaterial] by default. Any other [Material2d] can be used in its
+//! place by implementing [FlickerShader] for it and adding the plugin for it with
+//! [FlickerPlugin::with_material][crate::FlickerPlugin::with_material], which keeps the events,
+//! overlap actions and [RepeatingFlicker][crate::components::RepeatingFlicker] working as before.
+//!
+//! ```no_run
+//! use bevy::prelude::*;
+//! use bevy::render::render_resource::AsBindGroup;
+//! use bevy::shader::ShaderRef;
+//! use bevy::sprite_render::{AlphaMode2d, Material2d};
+//! use bevy_flicker::prelude::*;
+//!
+//! #[derive(Asset, AsBindGroup, TypePath, Clone)]
+//! struct HitMaterial {
+//!     #[texture(0)]
+//!     #[sampler(1)]
+//!     image: Option<Handle<Image>>,
+//!     #[uniform(2)]
+//!     color: LinearRgba,
+//! }
+//!
+//! impl Material2d for HitMaterial {
+//!     fn fragment_shader() -> ShaderRef {
+//!         "hit_material.wgsl".into()
+//!     }
+//!     fn alpha_mode(&self) -> AlphaMode2d {
+//!         AlphaMode2d::Blend
+//!     }
+//! }
+//!
+//! impl FlickerShader for HitMaterial {
+//!     fn from_inputs(inputs: &FlickerInputs) -> Self {
+//!         Self {
+//!             image: inputs.source_image.clone(),
+//!             color: inputs.color,
+//!         }
+//!     }
+//!
+//!     fn set_inputs(&mut self, inputs: &FlickerInputs) {
+//!         *self = Self::from_inputs(inputs);
+//!     }
+//! }
+//!
+//! App::new().add_plugins(FlickerPlugin::with_material::<HitMaterial>());
+//! ```
+
+use crate::{blend::FlickerBlendMode, style::FlickerStyle};
+
+use bevy_asset::Handle;
+use bevy_color::LinearRgba;
+use bevy_image::Image;
+use bevy_math::Vec2;
+use bevy_sprite_render::Material2d;
+
+/// Everything a 2D overlay material is given to draw a flicker
+#[derive(Clone, Debug, PartialEq)]
+pub struct FlickerInputs {
+    /// The image of the flickered sprite, None for meshes
+    pub source_image: Option<Handle<Image>>,
+
+    /// Where the part of the image the sprite shows starts, as a fraction of the image's size.
+    /// Add this to the mesh's uv multiplied by `size` to sample the image.
+    pub offset: Vec2,
+
+    /// The size of the part of the image the sprite shows, as a fraction of the image's size
+    pub size: Vec2,
+
+    /// The color of the flicker at its current point
+    pub color: LinearRgba,
+
+    /// How far along the flicker is, between 0.0 and 1.0
+    pub progress: f32,
+
+    /// How the flicker asked for its color to be combined with the sprite
+    pub blend_mode: FlickerBlendMode,
+
+    /// Which part of the sprite the flicker asked to be drawn over, always
+    /// [FlickerStyle::Fill] for meshes
+    pub style: FlickerStyle,
+}
+
+impl Default for FlickerInputs {
+    fn default() -> Self {
+        Self {
+            source_image: None,
+            offset: Vec2::ZERO,
+            size: Vec2::ONE,
+            color: LinearRgba::WHITE,
+            progress: 0.0,
+            blend_mode: FlickerBlendMode::default(),
+            style: FlickerStyle::default(),
+        }
+    }
+}
+
+/// A [Material2d] that 2D overlays can be drawn with, see the [module docs][self]
+pub trait FlickerShader: Material2d {
+    /// Creates the material of a new overlay
+    fn from_inputs(inputs: &FlickerInputs) -> Self;
+
+    /// Updates the material of an overlay after its inputs changed, such as when the color of
+    /// the flicker changes over time or its sprite moves on to the next frame of an animation.
+    ///
+    /// Materials of finished overlays are also reused for new overlays with this.
+    fn set_inputs(&mut self, inputs: &FlickerInputs);
+
+    /// Whether the material draws differently as the flicker progresses. When false, changes to
+    /// [FlickerInputs::progress] alone do not update the material, which saves it from being
+    /// prepared again every frame.
+    fn uses_progress(_inputs: &FlickerInputs) -> bool {
+        true
+    }
+}
@@ -0,0 +1,239 @@
+//! Works out where a sprite is drawn and which part of its image is drawn there, following how
+//! bevy renders sprites so that an overlay covers the sprite exactly.
+
+use bevy_asset::RenderAssetUsages;
+use bevy_math::{primitives::Rectangle, Rect, Vec2, Vec3, Vec4};
+use bevy_mesh::{Indices, Mesh, PrimitiveTopology};
+use bevy_sprite::{Sprite, SpriteImageMode, SpriteScalingMode, TextureSlice};
+
+/// The mesh a sprite overlay is drawn on
+#[derive(Clone)]
+pub(crate) enum SpriteMesh {
+    /// A single quad of the given size centered on the given point. These are shared between
+    /// overlays, so the texture coordinates are left to the material.
+    Quad { size: Vec2, center: Vec2 },
+
+    /// The slices of a sliced or tiled sprite, with texture coordinates covering the whole image
+    Slices(Mesh),
+}
+
+impl SpriteMesh {
+    /// Builds the mesh of a quad
+    pub(crate) fn quad_mesh(size: Vec2, center: Vec2) -> Mesh {
+        Mesh::from(Rectangle::new(size.x, size.y)).translated_by(center.extend(0.0))
+    }
+}
+
+/// The part of the image that a single quad sprite samples, as the offset and size of the
+/// texture coordinates, along with the quad it is drawn on.
+pub(crate) fn quad(
+    sprite: &Sprite,
+    anchor: Vec2,
+    image_size: Vec2,
+    texture_rect: Rect,
+) -> (Vec2, Vec2, SpriteMesh) {
+    // This follows the sprite renderer, so the texture coordinates start out from the bottom
+    // left of the rect and are converted at the end.
+    let texture_size = texture_rect.size();
+    let mut uv_offset_scale = Vec4::new(
+        texture_rect.min.x / image_size.x,
+        texture_rect.max.y / image_size.y,
+        texture_size.x / image_size.x,
+        -texture_size.y / image_size.y,
+    );
+    if sprite.flip_x {
+        uv_offset_scale.x += uv_offset_scale.z;
+        uv_offset_scale.z *= -1.0;
+    }
+    if sprite.flip_y {
+        uv_offset_scale.y += uv_offset_scale.w;
+        uv_offset_scale.w *= -1.0;
+    }
+
+    let mut quad_size = sprite.custom_size.unwrap_or(texture_size);
+    let mut quad_translation = Vec2::ZERO;
+    if let Some(scaling_mode) = sprite.image_mode.scale() {
+        apply_scaling(
+            scaling_mode,
+            texture_size,
+            &mut quad_size,
+            &mut quad_translation,
+            &mut uv_offset_scale,
+        );
+    }
+    let corner = (quad_size + quad_translation) * (-anchor - Vec2::splat(0.5));
+
+    (
+        Vec2::new(uv_offset_scale.x, uv_offset_scale.y + uv_offset_scale.w),
+        Vec2::new(uv_offset_scale.z, -uv_offset_scale.w),
+        SpriteMesh::Quad {
+            size: quad_size,
+            center: corner + quad_size * 0.5,
+        },
+    )
+}
+
+/// Builds the mesh of a sliced or tiled sprite, with one quad for each slice.
+///
+/// `default_size` is the size that a tiled sprite without a custom size is drawn at.
+pub(crate) fn slices(
+    sprite: &Sprite,
+    anchor: Vec2,
+    image_size: Vec2,
+    texture_rect: Rect,
+    default_size: Vec2,
+) -> SpriteMesh {
+    let slices = match &sprite.image_mode {
+        SpriteImageMode::Sliced(slicer) => slicer.compute_slices(texture_rect, sprite.custom_size),
+        SpriteImageMode::Tiled {
+            tile_x,
+            tile_y,
+            stretch_value,
+        } => TextureSlice {
+            texture_rect,
+            draw_size: sprite.custom_size.unwrap_or(default_size),
+            offset: Vec2::ZERO,
+        }
+        .tiled(*stretch_value, (*tile_x, *tile_y)),
+        _ => Vec::new(),
+    };
+
+    let mut flip = Vec2::ONE;
+    if sprite.flip_x {
+        flip.x *= -1.0;
+    }
+    if sprite.flip_y {
+        flip.y *= -1.0;
+    }
+    // Sliced sprites are anchored by their custom size or rect only, as the renderer does
+    let anchor = anchor
+        * sprite
+            .custom_size
+            .unwrap_or(sprite.rect.unwrap_or_default().size());
+
+    let mut positions = Vec::with_capacity(slices.len() * 4);
+    let mut normals = Vec::with_capacity(slices.len() * 4);
+    let mut uvs = Vec::with_capacity(slices.len() * 4);
+    let mut indices = Vec::with_capacity(slices.len() * 6);
+    for slice in slices {
+        let center = slice.offset * flip - anchor;
+        let min = center - slice.draw_size * 0.5;
+        let max = center + slice.draw_size * 0.5;
+
+        let mut uv_min = slice.texture_rect.min / image_size;
+        let mut uv_max = slice.texture_rect.max / image_size;
+        if sprite.flip_x {
+            std::mem::swap(&mut uv_min.x, &mut uv_max.x);
+        }
+        if sprite.flip_y {
+            std::mem::swap(&mut uv_min.y, &mut uv_max.y);
+        }
+
+        let start = positions.len() as u32;
+        positions.extend([
+            [max.x, max.y, 0.0],
+            [min.x, max.y, 0.0],
+            [min.x, min.y, 0.0],
+            [max.x, min.y, 0.0],
+        ]);
+        normals.extend([Vec3::Z.to_array(); 4]);
+        uvs.extend([
+            [uv_max.x, uv_min.y],
+            [uv_min.x, uv_min.y],
+            [uv_min.x, uv_max.y],
+            [uv_max.x, uv_max.y],
+        ]);
+        indices.extend([start, start + 1, start + 2, start, start + 2, start + 3]);
+    }
+
+    SpriteMesh::Slices(
+        Mesh::new(
+            PrimitiveTopology::TriangleList,
+            RenderAssetUsages::default(),
+        )
+        .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, positions)
+        .with_inserted_attribute(Mesh::ATTRIBUTE_NORMAL, normals)
+        .with_inserted_attribute(Mesh::ATTRIBUTE_UV_0, uvs)
+        .with_inserted_indices(Indices::U32(indices)),
+    )
+}
+
+/// Fits the texture to the quad for [SpriteImageMode::Scale], the same way the sprite renderer
+/// does.
+fn apply_scaling(
+    scaling_mode: SpriteScalingMode,
+    texture_size: Vec2,
+    quad_size: &mut Vec2,
+    quad_translation: &mut Vec2,
+    uv_offset_scale: &mut Vec4,
+) {
+    let quad_ratio = quad_size.x / quad_size.y;
+    let texture_ratio = texture_size.x / texture_size.y;
+    let tex_quad_scale = texture_ratio / quad_ratio;
+    let quad_tex_scale = quad_ratio / texture_ratio;
+
+    match scaling_mode {
+        SpriteScalingMode::FillCenter => {
+            if quad_ratio > texture_ratio {
+                uv_offset_scale.y += (uv_offset_scale.w - uv_offset_scale.w * tex_quad_scale) * 0.5;
+                uv_offset_scale.w *= tex_quad_scale;
+            } else {
+                uv_offset_scale.x += (uv_offset_scale.z - uv_offset_scale.z * quad_tex_scale) * 0.5;
+                uv_offset_scale.z *= quad_tex_scale;
+            };
+        }
+        SpriteScalingMode::FillStart => {
+            if quad_ratio > texture_ratio {
+                uv_offset_scale.y += uv_offset_scale.w - uv_offset_scale.w * tex_quad_scale;
+                uv_offset_scale.w *= tex_quad_scale;
+            } else {
+                uv_offset_scale.z *= quad_tex_scale;
+            }
+        }
+        SpriteScalingMode::FillEnd => {
+            if quad_ratio > texture_ratio {
+                uv_offset_scale.w *= tex_quad_scale;
+            } else {
+                uv_offset_scale.x += uv_offset_scale.z - uv_offset_scale.z * quad_tex_scale;
+                uv_offset_scale.z *= quad_tex_scale;
+            }
+        }
+        SpriteScalingMode::FitCenter => {
+            if texture_ratio > quad_ratio {
+                quad_size.y *= quad_tex_scale;
+            } else {
+                quad_size.x *= tex_quad_scale;
+            }
+        }
+        SpriteScalingMode::FitStart => {
+            let scale = if texture_ratio > quad_ratio {
+                Vec2::new(1.0, quad_tex_scale)
+            } else {
+                Vec2::new(tex_quad_scale, 1.0)
+            };
+            let new_quad = *quad_size * scale;
+            let offset = *quad_size - new_quad;
+            *quad_translation = if texture_ratio > quad_ratio {
+                Vec2::new(0.0, -offset.y)
+            } else {
+                Vec2::new(offset.x, 0.0)
+            };
+            *quad_size = new_quad;
+        }
+        SpriteScalingMode::FitEnd => {
+            let scale = if texture_ratio > quad_ratio {
+                Vec2::new(1.0, quad_tex_scale)
+            } else {
+                Vec2::new(tex_quad_scale, 1.0)
+            };
+            let new_quad = *quad_size * scale;
+            let offset = *quad_size - new_quad;
+            *quad_translation = if texture_ratio > quad_ratio {
+                Vec2::new(0.0, offset.y)
+            } else {
+                Vec2::new(-offset.x, 0.0)
+            };
+            *quad_size = new_quad;
+        }
+    }
+}
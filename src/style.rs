@@ -0,0 +1,63 @@
+use bevy_asset::Handle;
+use bevy_image::Image;
+use bevy_reflect::Reflect;
+
+/// Which part of the sprite the flicker is drawn over.
+///
+/// Styles other than [FlickerStyle::Fill] only apply to sprite overlays, other overlays and
+/// tinted flickers are always filled.
+#[derive(Clone, Debug, Default, PartialEq, Reflect)]
+pub enum FlickerStyle {
+    /// Draws the flicker over the whole sprite.
+    #[default]
+    Fill,
+
+    /// Draws the flicker only along the edge of the sprite, where its alpha changes from opaque
+    /// to transparent. The outline is drawn along the inside of the edge and is `thickness`
+    /// pixels of the sprite's image wide.
+    Outline { thickness: f32 },
+
+    /// Masks the flicker with noise that eats away at it as the flicker progresses, for a
+    /// dissolve or static look. The flicker starts out whole and is gone by the time it ends.
+    ///
+    /// The red channel of `noise` is used as the mask, repeating across the sprite, or built-in
+    /// noise when it is None. `scale` is the size in pixels of the sprite's image that one
+    /// repeat of the noise image, or one cell of the built-in noise, covers.
+    Dissolve {
+        noise: Option<Handle<Image>>,
+        scale: f32,
+    },
+}
+
+impl FlickerStyle {
+    /// The value the shader uses to identify this style
+    pub(crate) fn shader_index(&self) -> u32 {
+        match self {
+            Self::Fill => 0,
+            Self::Outline { .. } => 1,
+            Self::Dissolve { noise: None, .. } => 2,
+            Self::Dissolve { noise: Some(_), .. } => 3,
+        }
+    }
+
+    /// The width of the outline in pixels of the sprite's image, 0.0 for styles without one
+    pub(crate) fn outline_thickness(&self) -> f32 {
+        match self {
+            Self::Outline { thickness } => thickness.max(0.0),
+            Self::Fill | Self::Dissolve { .. } => 0.0,
+        }
+    }
+
+    /// The noise image and the size it covers, for styles that use noise
+    pub(crate) fn noise(&self) -> (Option<Handle<Image>>, f32) {
+        match self {
+            Self::Dissolve { noise, scale } => (noise.clone(), scale.max(f32::EPSILON)),
+            Self::Fill | Self::Outline { .. } => (None, 1.0),
+        }
+    }
+
+    /// Whether the look of the style changes as the flicker progresses
+    pub(crate) fn is_animated(&self) -> bool {
+        matches!(self, Self::Dissolve { .. })
+    }
+}
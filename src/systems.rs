@@ -1,185 +1,512 @@
 use crate::{
-    components::{FlickerMarker, Flickered, NoFlicker, RepeatingFlicker},
-    config::FlickerPluginConfig,
-    events::FlickerStartEvent,
-    flicker::FlickerMaterial,
+    clock::FlickerClock,
+    components::{
+        Flicker, FlickerGroup, FlickerGroupMember, FlickerMarker, FlickerOverlapOverride,
+        FlickerOverlay, FlickerQueue, Flickered, PersistentOverlay, RepeatingFlicker,
+    },
+    config::{FlickerOverlapAction, FlickerPluginConfig},
+    events::{FlickerLifecycle, FlickerPendingStarts, FlickerStartEvent, FlickerStopEvent},
+    overlay::{
+        FlickerAssetCache, IsActiveOverlay, IsOverlay, OverlayAssets, OverlayMaterials,
+        OverlaySources, OverlayState, SpriteChanged,
+    },
+    shader::FlickerShader,
+    tint::{FlickerTint, FlickerTints},
 };
 
+use bevy_asset::{AssetServer, Assets};
+use bevy_camera::visibility::Visibility;
+
 use bevy_ecs::{
+    change_detection::DetectChanges,
     entity::Entity,
     hierarchy::{ChildOf, Children},
-    query::{With, Without},
+    lifecycle::Remove,
+    observer::On,
+    query::Without,
+    relationship::RelationshipTarget,
     system::{Commands, Query, Res, ResMut},
 };
-use bevy_sprite_render::MeshMaterial2d;
 
-use bevy_ecs::message::{MessageWriter, MessageReader};
+use bevy_color::Alpha;
 
-use bevy_asset::Assets;
-use bevy_image::{Image, TextureAtlasLayout};
-use bevy_log::{error, warn};
-use bevy_math::{primitives::Rectangle, URect, Vec2, Vec3};
-use bevy_sprite::Sprite;
-use bevy_time::Time;
-use bevy_transform::components::Transform;
+use bevy_ecs::message::{MessageReader, MessageWriter};
+
+use bevy_log::warn;
+use bevy_mesh::{Mesh, Mesh2d};
+use bevy_sprite::{Anchor, Sprite};
+use bevy_sprite_render::MeshMaterial2d;
 
-use bevy_mesh::{Mesh2d, Mesh};
+use std::collections::HashSet;
 
-pub(crate) fn flicker_start(
-    sprites: Query<&Sprite, Without<NoFlicker>>,
-    mesh_components: Query<&Mesh2d, Without<NoFlicker>>,
-    mut flicker_materials: ResMut<Assets<FlickerMaterial>>,
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn flicker_start<M: FlickerShader>(
+    sources: OverlaySources,
+    mut assets: OverlayAssets<M>,
     mut flicker_start_events: MessageReader<FlickerStartEvent>,
-    mut meshes: ResMut<Assets<Mesh>>,
-    images: Res<Assets<Image>>,
-    atlas_layouts: Res<Assets<TextureAtlasLayout>>,
     mut commands: Commands,
-    flickereds: Query<&FlickerMarker>,
     config: Res<FlickerPluginConfig>,
+    overlap_overrides: Query<&FlickerOverlapOverride>,
     with_children: Query<&Children>,
-    flicker_children: Query<Entity, With<Flickered>>,
+    flicker_children: Query<Entity, IsOverlay>,
+    mut active_flickers: Query<&mut Flickered>,
+    mut queues: Query<&mut FlickerQueue>,
+    mut flicker_overlays: Query<&mut FlickerOverlay>,
+    overlay_state: Query<(OverlayState, OverlayMaterials<M>)>,
+    mut lifecycle: FlickerLifecycle,
+    mut held: ResMut<FlickerPendingStarts>,
 ) {
-    for e in flicker_start_events.read() {
-        if flickereds.get(e.entity).is_ok() && config.ignore_overlap() {
-            // We ignore this flicker event entirely.
+    // Overlays spawned by this system are not visible to it until its commands are applied, so
+    // an entity is only started once per run and any further events for it are held until the
+    // next run, where the overlap action can take the earlier flicker into account.
+    let mut started = HashSet::new();
+    let pending = std::mem::take(&mut held.0);
+    for e in pending.iter().chain(flicker_start_events.read()) {
+        if started.contains(&e.entity) {
+            held.0.push(e.clone());
             continue;
         }
 
-        // Get image handle or image handle save
-        let (material, mesh) = if let Ok(sprite) = sprites.get(e.entity) {
-            let image_handle = &sprite.image;
-            let img = if let Some(img) = images.get(image_handle) {
-                img
-            } else {
-                error!("Could not get image from image handle to begin flicker");
-                continue;
-            };
+        // The flickers currently running on the entity, ignoring any that are fading out after
+        // being stopped.
+        let active = with_children
+            .get(e.entity)
+            .into_iter()
+            .flat_map(|children| children.iter())
+            .filter(|child| {
+                active_flickers
+                    .get(*child)
+                    .is_ok_and(|flickered| !flickered.is_stopping())
+            })
+            .collect::<Vec<_>>();
 
-            if let Some(texture_atlas) = sprite.texture_atlas.as_ref() {
-                let index = texture_atlas.index;
-                if let Some(atlas) = atlas_layouts.get(&texture_atlas.layout) {
-                    let curr_rect = atlas
-                        .textures
-                        .get(index)
-                        .copied()
-                        .unwrap_or(URect::new(0, 0, 0, 0));
-                    let rect_size = Vec2::new(curr_rect.width() as f32, curr_rect.height() as f32);
-                    let img_size = img.size().as_vec2();
-                    let ratio = img_size / rect_size;
-                    let offset = curr_rect.min.as_vec2() / img_size;
-                    let size = rect_size / img_size;
-                    let mesh_size = sprite.custom_size.unwrap_or(rect_size);
-                    (
-                        FlickerMaterial {
-                            source_image: Some(image_handle.clone()),
-                            offset,
-                            size,
-                            ratio,
-                            color: e.color.into(),
-                            ..Default::default()
-                        },
-                        Mesh::from(Rectangle::new(mesh_size.x, mesh_size.y)),
-                    )
-                } else {
-                    error!(
-                        "Could not get atlas to determine which part of sprite is currently active"
-                    );
+        let overlap_action = e
+            .overlap_action
+            .or_else(|| overlap_overrides.get(e.entity).ok().map(|o| o.0))
+            .unwrap_or(config.overlap_action);
+        let mut layer = 0;
+        if !active.is_empty() {
+            match overlap_action {
+                FlickerOverlapAction::Overwrite => {}
+                FlickerOverlapAction::Ignore => {
+                    // We ignore this flicker event entirely.
                     continue;
                 }
-            } else {
-                // No texture atlas, so go with the whole image.
-                let mesh_size = sprite.custom_size.unwrap_or(img.size().as_vec2());
-                (
-                    FlickerMaterial {
-                        source_image: Some(image_handle.clone()),
-                        color: e.color.into(),
-                        ..Default::default()
-                    },
-                    Mesh::from(Rectangle::new(mesh_size.x, mesh_size.y)),
-                )
+                FlickerOverlapAction::Queue => {
+                    if let Ok(mut queue) = queues.get_mut(e.entity) {
+                        queue.0.push_back(e.clone());
+                    } else {
+                        commands
+                            .entity(e.entity)
+                            .insert(FlickerQueue([e.clone()].into()));
+                    }
+                    continue;
+                }
+                FlickerOverlapAction::Extend => {
+                    for child in active {
+                        if let Ok(mut flickered) = active_flickers.get_mut(child) {
+                            flickered.extend(e.secs);
+                        }
+                    }
+                    continue;
+                }
+                FlickerOverlapAction::KeepLongest => {
+                    let remaining = active
+                        .iter()
+                        .filter_map(|child| active_flickers.get(*child).ok())
+                        .map(|flickered| flickered.remaining_secs())
+                        .fold(0.0, f32::max);
+                    if e.secs <= remaining {
+                        continue;
+                    }
+                }
+                FlickerOverlapAction::KeepStrongest => {
+                    let strength = active
+                        .iter()
+                        .filter_map(|child| active_flickers.get(*child).ok())
+                        .map(|flickered| flickered.current_color().alpha())
+                        .fold(0.0, f32::max);
+                    if e.strength() <= strength {
+                        continue;
+                    }
+                }
+                FlickerOverlapAction::Stack => {
+                    // Above every overlay still drawn on the entity, as lower layers may have
+                    // finished while higher ones are still running
+                    layer = with_children
+                        .get(e.entity)
+                        .into_iter()
+                        .flat_map(|children| children.iter())
+                        .filter_map(|child| active_flickers.get(child).ok())
+                        .map(|flickered| flickered.layer + 1)
+                        .max()
+                        .unwrap_or(0);
+                }
             }
-        } else if let Ok(mesh_handle) = mesh_components.get(e.entity) {
-            if let Some(mesh) = meshes.get(&mesh_handle.0).cloned() {
-                (
-                    FlickerMaterial {
-                        color: e.color.into(),
-                        ..Default::default()
-                    },
-                    mesh,
-                )
-            } else {
-                error!("Entity {:?} had an invalid mesh handle", e.entity);
-                continue;
+        }
+
+        let flickered = Flickered::from_event(e, layer);
+
+        // Find every entity that will receive an overlay, starting with the entity itself
+        let mut targets = Vec::new();
+        if sources.is_flickerable(e.entity) {
+            targets.push(e.entity);
+        }
+        if e.recursive && !sources.is_no_flicker(e.entity) {
+            let mut stack = vec![e.entity];
+            while let Some(parent) = stack.pop() {
+                let Ok(children) = with_children.get(parent) else {
+                    continue;
+                };
+                for child in children.iter() {
+                    // Skip the overlays of other flickers and any subtree that opted out
+                    if flicker_children.contains(child) || sources.is_no_flicker(child) {
+                        continue;
+                    }
+                    if sources.is_flickerable(child) {
+                        targets.push(child);
+                    }
+                    stack.push(child);
+                }
             }
-        } else {
+        } else if !e.recursive {
+            // Text split into several spans keeps all but the first in children of its own
+            targets.extend(sources.text_spans(e.entity));
+        }
+
+        if targets.is_empty() {
             warn!(
                 "Attempted to flicker on a despawned or sprite-less entity {:?}",
                 e.entity
             );
             continue;
-        };
+        }
 
-        if !config.ignore_overlap() {
+        let overlays = targets
+            .into_iter()
+            .filter_map(|target| {
+                sources
+                    .overlay(target, &flickered, e.blend_mode, &assets.meshes)
+                    .map(|overlay| (target, overlay))
+            })
+            .collect::<Vec<_>>();
+        if overlays.is_empty() {
+            continue;
+        }
+
+        if layer == 0 {
             // Despawn any previous flickering children
             if let Ok(children) = with_children.get(e.entity) {
                 // Iterate over the children and remove any flickers
                 for child in children {
                     if flicker_children.contains(*child) {
-                        if let Ok(mut entity_commands) = commands.get_entity(*child) {
-                            entity_commands.despawn();
+                        if let Ok(flickered) = active_flickers.get(*child) {
+                            if !flickered.is_stopping() {
+                                lifecycle.interrupted(&mut commands, flickered);
+                            }
                         }
+                        let ((persistent, group), _) =
+                            overlay_state.get(*child).unwrap_or_default();
+                        retire_overlay(&mut commands, *child, persistent, group);
                     }
                 }
             }
         }
 
-        if let Ok(mut entity_commands) = commands.get_entity(e.entity) {
-            entity_commands.with_children(|parent| {
-                parent.spawn((
-                    MeshMaterial2d(flicker_materials.add(material)),
-                    Mesh2d(meshes.add(mesh)),
-                    Transform {
-                        // Translation is relative to its parent, so 1.0 guarantees it is always in
-                        // front of its parent.
-                        translation: Vec3::new(0.0, 0.0, 1.0),
-                        ..Default::default()
-                    },
-                    Flickered::with_secs(e.secs),
-                ));
+        let Ok(mut entity_commands) = commands.get_entity(e.entity) else {
+            continue;
+        };
+        entity_commands.insert(FlickerMarker);
+
+        // Entities with a FlickerOverlay reuse the same overlay for their bottom layer
+        let mut persistent = if layer == 0 {
+            flicker_overlays.get_mut(e.entity).ok()
+        } else {
+            None
+        };
+        let reused = persistent
+            .as_ref()
+            .and_then(|flicker_overlay| flicker_overlay.overlay)
+            .filter(|overlay| {
+                overlay_state
+                    .get(*overlay)
+                    .is_ok_and(|((persistent, _), _)| persistent)
             });
-            entity_commands.insert(FlickerMarker);
+
+        // The timer lives on the overlay of the flickered entity itself, or on an entity
+        // without an overlay if only its descendants could be flickered.
+        let lead = match reused {
+            Some(lead) => {
+                commands
+                    .entity(lead)
+                    .insert((flickered, Visibility::Inherited));
+                lead
+            }
+            None => {
+                let lead = commands.spawn((flickered, ChildOf(e.entity))).id();
+                if let Some(flicker_overlay) = persistent.as_mut() {
+                    flicker_overlay.overlay = Some(lead);
+                    commands.entity(lead).insert(PersistentOverlay);
+                }
+                lead
+            }
+        };
+        let existing = reused
+            .and_then(|lead| overlay_state.get(lead).ok())
+            .map(|(_, materials)| materials)
+            .unwrap_or_default();
+        let z_offset = e.z_offset.unwrap_or(config.z_offset);
+        for (target, overlay) in overlays {
+            let (mut overlay_commands, existing) = if target == e.entity {
+                (commands.entity(lead), existing)
+            } else {
+                let member = commands.spawn((ChildOf(target), FlickerGroupMember(lead)));
+                (member, Default::default())
+            };
+            assets.insert(&mut overlay_commands, overlay, z_offset, layer, existing);
+            sources.copy_render_layers(target, &mut overlay_commands);
         }
+        started.insert(e.entity);
+        lifecycle.started(&mut commands, e);
     }
 }
 
-pub(crate) fn flicker_tick(
-    mut flickered: Query<(&ChildOf, Entity, &mut Flickered)>,
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn flicker_tick<M: FlickerShader>(
+    mut flickered: Query<(
+        &ChildOf,
+        Entity,
+        &mut Flickered,
+        OverlayMaterials<M>,
+        OverlayState,
+    )>,
+    members: Query<OverlayMaterials<M>, Without<Flickered>>,
+    with_children: Query<&Children>,
+    mut queues: Query<&mut FlickerQueue>,
+    mut assets: OverlayAssets<M>,
     mut commands: Commands,
-    time: Res<Time>,
+    mut lifecycle: FlickerLifecycle,
+    mut flicker_start_event_writer: MessageWriter<FlickerStartEvent>,
+    mut tints: FlickerTints,
+    clock: FlickerClock,
 ) {
-    for (child_of, entity, mut flickered) in flickered.iter_mut() {
-        flickered.0.tick(time.delta());
-        if flickered.0.is_finished() {
-            if let Ok(mut entity_commands) = commands.get_entity(entity) {
-                entity_commands.despawn();
+    // Kept in the order the flickers finished in, so that queued flickers are started in the
+    // same order every run
+    let mut finished_parents = Vec::new();
+    for (child_of, entity, mut flickered, materials, (persistent, group)) in flickered.iter_mut() {
+        // Flickers started this frame are drawn once at their starting point before they
+        // advance, otherwise a flicker no longer than a frame would end before it is ever drawn.
+        if !flickered.is_added() {
+            // The clock is chosen by the flickered entity rather than its overlay
+            flickered.tick(clock.delta(child_of.0));
+        }
+        // Push the current point of the gradient, intensity curve and style into the material,
+        // which is only touched if it changed
+        let color = flickered.current_color();
+        let progress = flickered.progress();
+        assets.animate(materials, color, progress);
+        for member in group.into_iter().flat_map(|group| group.iter()) {
+            if let Ok(materials) = members.get(member) {
+                assets.animate(materials, color, progress);
             }
-            if let Ok(mut entity_commands) = commands.get_entity(child_of.0) {
-                entity_commands.remove::<FlickerMarker>();
+        }
+        // Tints are pushed every tick, so that when one of two stacked tints finishes the one
+        // left takes over the color of their target.
+        tints.set_color(entity, color, &mut assets.color_materials);
+        for member in group.into_iter().flat_map(|group| group.iter()) {
+            tints.set_color(member, color, &mut assets.color_materials);
+        }
+        if flickered.is_finished() {
+            retire_overlay(&mut commands, entity, persistent, group);
+            if !flickered.is_stopping() {
+                // Stopped flickers already reported they were interrupted
+                lifecycle.ended(&mut commands, &flickered);
+            }
+            if !finished_parents.contains(&child_of.0) {
+                finished_parents.push(child_of.0);
             }
         }
     }
+
+    for parent in finished_parents {
+        // Stacked flickers finish on their own, the entity is only done once all of them are
+        let still_flickering = with_children
+            .get(parent)
+            .into_iter()
+            .flat_map(|children| children.iter())
+            .any(|child| {
+                flickered
+                    .get(child)
+                    .is_ok_and(|(_, _, flickered, ..)| !flickered.is_finished())
+            });
+        if still_flickering {
+            continue;
+        }
+        if let Ok(mut entity_commands) = commands.get_entity(parent) {
+            entity_commands.remove::<FlickerMarker>();
+        }
+        if let Some(next) = queues
+            .get_mut(parent)
+            .ok()
+            .and_then(|mut queue| queue.0.pop_front())
+        {
+            flicker_start_event_writer.write(next);
+        }
+    }
+}
+
+pub(crate) fn flicker_stop(
+    mut flicker_stop_events: MessageReader<FlickerStopEvent>,
+    with_children: Query<&Children>,
+    mut active_flickers: Query<&mut Flickered>,
+    overlay_state: Query<OverlayState>,
+    mut queues: Query<&mut FlickerQueue>,
+    mut commands: Commands,
+    mut lifecycle: FlickerLifecycle,
+) {
+    for e in flicker_stop_events.read() {
+        let Ok(children) = with_children.get(e.entity) else {
+            continue;
+        };
+        for child in children.iter() {
+            let Ok(mut flickered) = active_flickers.get_mut(child) else {
+                continue;
+            };
+            if flickered.is_stopping() {
+                // Already fading out from an earlier stop
+                continue;
+            }
+            lifecycle.interrupted(&mut commands, &flickered);
+            if let Ok(mut queue) = queues.get_mut(e.entity) {
+                // Anything waiting to play would otherwise start once this one is gone
+                queue.0.clear();
+            }
+            match e.fade_secs {
+                Some(fade_secs) if fade_secs > 0.0 => flickered.stop(fade_secs),
+                _ => {
+                    let (persistent, group) = overlay_state.get(child).unwrap_or_default();
+                    retire_overlay(&mut commands, child, persistent, group);
+                    commands.entity(e.entity).remove::<FlickerMarker>();
+                }
+            }
+        }
+    }
+}
+
+/// Keeps sprite overlays in step with their sprite, such as when an animation moves on to the
+/// next frame of its atlas part way through a flicker.
+pub(crate) fn flicker_sprite_sync<M: FlickerShader>(
+    sources: OverlaySources,
+    mut assets: OverlayAssets<M>,
+    changed_sprites: Query<(&Sprite, &Anchor, &Children), SpriteChanged>,
+    active_overlays: Query<(&MeshMaterial2d<M>, &Mesh2d), IsActiveOverlay>,
+    mut commands: Commands,
+) {
+    for (sprite, anchor, children) in changed_sprites.iter() {
+        let mut overlays = children
+            .iter()
+            .filter_map(|child| active_overlays.get(child).ok().map(|m| (child, m)))
+            .peekable();
+        if overlays.peek().is_none() {
+            continue;
+        }
+        let Some((geometry, mesh)) = sources.sprite_geometry(sprite, anchor) else {
+            continue;
+        };
+        for (overlay, rendered) in overlays {
+            assets.set_sprite_geometry(
+                &mut commands.entity(overlay),
+                rendered,
+                geometry.clone(),
+                mesh.clone(),
+            );
+        }
+    }
+}
+
+/// Takes down the overlay of a flicker that is over. Overlays kept alive by [FlickerOverlay] are
+/// hidden instead, so that the next flicker can reuse them.
+fn retire_overlay(
+    commands: &mut Commands,
+    overlay: Entity,
+    persistent: bool,
+    group: Option<&FlickerGroup>,
+) {
+    if !persistent {
+        // Despawning also despawns any overlays of a recursive flicker
+        if let Ok(mut entity_commands) = commands.get_entity(overlay) {
+            entity_commands.despawn();
+        }
+        return;
+    }
+    for member in group.into_iter().flat_map(|group| group.iter()) {
+        commands.entity(member).despawn();
+    }
+    if let Ok(mut entity_commands) = commands.get_entity(overlay) {
+        entity_commands
+            .remove::<Flickered>()
+            .insert(Visibility::Hidden);
+        entity_commands.remove::<FlickerTint>();
+    }
+}
+
+/// Returns the material of an overlay to the pool when the overlay is despawned
+pub(crate) fn recycle_overlay_material<M: FlickerShader>(
+    remove: On<Remove, MeshMaterial2d<M>>,
+    overlays: Query<&MeshMaterial2d<M>>,
+    mut cache: ResMut<FlickerAssetCache<M>>,
+) {
+    if let Ok(material) = overlays.get(remove.entity) {
+        cache.recycle(material.0.clone());
+    }
+}
+
+/// Puts back the color of a tinted entity once its flicker is over
+pub(crate) fn restore_tint(remove: On<Remove, FlickerTint>, mut tints: FlickerTints) {
+    tints.restore(remove.entity);
+}
+
+/// Starts the flickers of [Flicker] components once the entity they are on can be drawn, and
+/// drops them if something it is drawn from failed to load
+pub(crate) fn inserted_flicker_start(
+    flickers: Query<(Entity, &Flicker)>,
+    sources: OverlaySources,
+    meshes: Res<Assets<Mesh>>,
+    asset_server: Res<AssetServer>,
+    mut flicker_start_event_writer: MessageWriter<FlickerStartEvent>,
+    mut commands: Commands,
+) {
+    for (entity, flicker) in flickers.iter() {
+        let event = flicker.generate_start_event(entity);
+        let unloaded = sources.unloaded_assets(entity, event.recursive, &meshes);
+        if unloaded.is_empty() {
+            flicker_start_event_writer.write(event);
+        } else if let Some(failed) = unloaded
+            .into_iter()
+            .find(|id| asset_server.load_state(*id).is_failed())
+        {
+            warn!(
+                "Dropped the Flicker on {:?} as asset {:?} failed to load",
+                entity, failed
+            );
+        } else {
+            continue;
+        }
+        commands.entity(entity).remove::<Flicker>();
+    }
 }
 
 pub(crate) fn repeating_flicker_tick(
     mut repeating_flickers: Query<(Entity, &mut RepeatingFlicker)>,
     mut flicker_start_event_writer: MessageWriter<FlickerStartEvent>,
     mut commands: Commands,
-    time: Res<Time>,
+    clock: FlickerClock,
 ) {
     for (entity, mut repeating_flicker) in repeating_flickers.iter_mut() {
+        let delta = clock.delta(entity);
         if repeating_flicker.curr_pulse_count > 0 {
             // We still have flickers left in the current pulse.
-            repeating_flicker.timer.tick(time.delta());
+            repeating_flicker.timer.tick(delta);
             if repeating_flicker.timer.just_finished() {
                 // The pause has finished, flicker again
                 flicker_start_event_writer.write(repeating_flicker.generate_start_event(entity));
@@ -199,7 +526,7 @@ pub(crate) fn repeating_flicker_tick(
                 }
             }
         } else {
-            repeating_flicker.pulse_timer.tick(time.delta());
+            repeating_flicker.pulse_timer.tick(delta);
             if repeating_flicker.pulse_timer.just_finished() {
                 // We are ready for another pulse.
                 repeating_flicker.curr_pulse_count = repeating_flicker.pulse_count;
@@ -0,0 +1,169 @@
+//! Flickers that change the color of the flickered entity itself instead of drawing an overlay,
+//! either because the flicker asked for [FlickerMode::Tint][crate::mode::FlickerMode::Tint] or
+//! because there is nothing an overlay can be drawn over, such as text. The color is changed for
+//! the length of the flicker and then put back.
+
+use crate::blend::FlickerBlendMode;
+
+use bevy_asset::{Assets, Handle};
+use bevy_color::{Color, LinearRgba};
+use bevy_ecs::{
+    component::Component,
+    entity::Entity,
+    reflect::ReflectComponent,
+    system::{Query, SystemParam},
+};
+use bevy_reflect::Reflect;
+use bevy_sprite::Sprite;
+use bevy_sprite_render::{ColorMaterial, MeshMaterial2d};
+
+#[cfg(feature = "text")]
+use bevy_ecs::change_detection::DetectChangesMut;
+#[cfg(feature = "text")]
+use bevy_text::TextColor;
+
+/// What a tint changes on its target, along with what it was before the flicker
+#[derive(Clone, Debug, Reflect)]
+pub(crate) enum TintOriginal {
+    Sprite(Color),
+
+    /// The material of the target, which is swapped for a tinted copy while the flicker lasts
+    ColorMaterial(Handle<ColorMaterial>),
+
+    #[cfg(feature = "text")]
+    Text(Color),
+}
+
+/// Placed on the overlay entity of a flicker that recolors its target, holds on to what the
+/// target looked like before the flicker so that it can be put back once the flicker is over.
+#[derive(Component, Clone, Debug, Reflect)]
+#[reflect(Component)]
+pub struct FlickerTint {
+    pub(crate) target: Entity,
+    pub(crate) original: TintOriginal,
+    pub(crate) blend_mode: FlickerBlendMode,
+
+    /// The tinted copy of the target's [ColorMaterial], if it has one
+    pub(crate) material: Option<Handle<ColorMaterial>>,
+}
+
+impl FlickerTint {
+    /// The color of the target with the given flicker color drawn over it
+    pub(crate) fn tinted(
+        &self,
+        color: LinearRgba,
+        color_materials: &Assets<ColorMaterial>,
+    ) -> Color {
+        let original = match &self.original {
+            TintOriginal::Sprite(original) => *original,
+            TintOriginal::ColorMaterial(handle) => color_materials
+                .get(handle)
+                .map_or(Color::WHITE, |material| material.color),
+            #[cfg(feature = "text")]
+            TintOriginal::Text(original) => *original,
+        };
+        self.blend_mode.apply(original.into(), color).into()
+    }
+}
+
+/// The tints of running flickers and the colors they change
+#[derive(SystemParam)]
+pub(crate) struct FlickerTints<'w, 's> {
+    tints: Query<'w, 's, (Entity, &'static FlickerTint)>,
+    sprites: Query<'w, 's, &'static mut Sprite>,
+    mesh_materials: Query<'w, 's, &'static mut MeshMaterial2d<ColorMaterial>>,
+    #[cfg(feature = "text")]
+    text_colors: Query<'w, 's, &'static mut TextColor>,
+}
+
+impl FlickerTints<'_, '_> {
+    /// Recolors the target of the tint on the given overlay entity, if it has one
+    pub(crate) fn set_color(
+        &mut self,
+        overlay: Entity,
+        color: LinearRgba,
+        color_materials: &mut Assets<ColorMaterial>,
+    ) {
+        let Ok((_, tint)) = self.tints.get(overlay) else {
+            return;
+        };
+        let tinted = tint.tinted(color, color_materials);
+        match &tint.original {
+            TintOriginal::Sprite(_) => {
+                if let Ok(mut sprite) = self.sprites.get_mut(tint.target) {
+                    // Only touch the sprite when needed, so its overlays are not updated
+                    if sprite.color != tinted {
+                        sprite.color = tinted;
+                    }
+                }
+            }
+            TintOriginal::ColorMaterial(_) => {
+                let Some(handle) = tint.material.as_ref() else {
+                    return;
+                };
+                // Only touch the asset when needed, modifying it causes it to be prepared again
+                let stale = color_materials
+                    .get(handle)
+                    .is_some_and(|material| material.color != tinted);
+                if stale {
+                    if let Some(material) = color_materials.get_mut(handle) {
+                        material.color = tinted;
+                    }
+                }
+            }
+            #[cfg(feature = "text")]
+            TintOriginal::Text(_) => {
+                if let Ok(mut text_color) = self.text_colors.get_mut(tint.target) {
+                    text_color.set_if_neq(TextColor(tinted));
+                }
+            }
+        }
+    }
+
+    /// Puts back what the target of the tint on the given overlay entity looked like before it
+    /// was flickered. While another tint on the same target is still running the target is left
+    /// to that one instead, so the original color does not flash in between.
+    pub(crate) fn restore(&mut self, overlay: Entity) {
+        let Ok((_, tint)) = self.tints.get(overlay) else {
+            return;
+        };
+        let remaining = self
+            .tints
+            .iter()
+            .find(|(other, other_tint)| *other != overlay && other_tint.target == tint.target)
+            .map(|(_, other_tint)| other_tint);
+        if let Some(remaining) = remaining {
+            // A target with a tinted copy of its material is handed the copy of the remaining
+            // tint, the colors are pushed on its next tick
+            if let Some(material) = remaining.material.as_ref() {
+                if let Ok(mut mesh_material) = self.mesh_materials.get_mut(tint.target) {
+                    if tint.material.as_ref() == Some(&mesh_material.0) {
+                        mesh_material.0 = material.clone();
+                    }
+                }
+            }
+            return;
+        }
+        match &tint.original {
+            TintOriginal::Sprite(original) => {
+                if let Ok(mut sprite) = self.sprites.get_mut(tint.target) {
+                    sprite.color = *original;
+                }
+            }
+            TintOriginal::ColorMaterial(original) => {
+                if let Ok(mut mesh_material) = self.mesh_materials.get_mut(tint.target) {
+                    // Leave the material alone if it was replaced during the flicker
+                    if tint.material.as_ref() == Some(&mesh_material.0) {
+                        mesh_material.0 = original.clone();
+                    }
+                }
+            }
+            #[cfg(feature = "text")]
+            TintOriginal::Text(original) => {
+                if let Ok(mut text_color) = self.text_colors.get_mut(tint.target) {
+                    text_color.0 = *original;
+                }
+            }
+        }
+    }
+}